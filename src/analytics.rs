@@ -0,0 +1,435 @@
+pub mod metrika;
+pub mod plausible;
+
+use crate::data::AnalyticsProvider;
+use crate::url_query;
+use metrika::Metrika;
+use plausible::Plausible;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use yew::Callback;
+
+const ATTRIBUTION_KEY: &str = "yainna.utm";
+const CONSENT_KEY: &str = "yainna.consent";
+
+/// Счётчик посещаемости.
+pub trait Analytics {
+    /// Подключает скрипт счётчика; вызывается только после согласия посетителя.
+    fn load(&self) -> Result<(), String>;
+    fn reach_goal(&self, goal: &Goal);
+}
+
+/// Аналитика выключена.
+pub struct NoAnalytics;
+
+impl Analytics for NoAnalytics {
+    fn load(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn reach_goal(&self, _goal: &Goal) {}
+}
+
+pub fn backend(provider: &AnalyticsProvider) -> Box<dyn Analytics> {
+    match provider {
+        AnalyticsProvider::Metrika {
+            counter_id,
+            webvisor,
+        } => Box::new(Metrika {
+            counter_id: *counter_id,
+            webvisor: *webvisor,
+        }),
+        AnalyticsProvider::Plausible { domain, script } => Box::new(Plausible {
+            domain: domain.clone(),
+            script: script.clone(),
+        }),
+        AnalyticsProvider::None => Box::new(NoAnalytics),
+    }
+}
+
+/// Решение посетителя в баннере согласия.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Consent {
+    Accepted,
+    Declined,
+}
+
+impl Consent {
+    fn as_str(self) -> &'static str {
+        match self {
+            Consent::Accepted => "accepted",
+            Consent::Declined => "declined",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "accepted" => Some(Consent::Accepted),
+            "declined" => Some(Consent::Declined),
+            _ => None,
+        }
+    }
+}
+
+/// Настроенный счётчик; цели уходят в него только после загрузки скрипта.
+struct Tracker {
+    backend: Box<dyn Analytics>,
+    loaded: bool,
+}
+
+thread_local! {
+    static TRACKER: RefCell<Tracker> = RefCell::new(Tracker {
+        backend: Box::new(NoAnalytics),
+        loaded: false,
+    });
+    static ATTRIBUTION: RefCell<Option<Attribution>> = const { RefCell::new(None) };
+}
+
+/// Выбирает счётчик из настроек. Если посетитель уже согласился раньше, скрипт
+/// подключается сразу.
+pub fn configure(provider: &AnalyticsProvider) {
+    TRACKER.with(|tracker| tracker.borrow_mut().backend = backend(provider));
+    if consent() == Some(Consent::Accepted) {
+        load();
+    }
+}
+
+/// Сохранённое решение посетителя; `None` — баннер ещё не показывался.
+pub fn consent() -> Option<Consent> {
+    storage()?
+        .get_item(CONSENT_KEY)
+        .ok()?
+        .as_deref()
+        .and_then(Consent::parse)
+}
+
+pub fn set_consent(consent: Consent) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(CONSENT_KEY, consent.as_str());
+    }
+
+    match consent {
+        Consent::Accepted => {
+            persist_attribution();
+            load();
+        }
+        // Загруженный скрипт счётчика не выгрузить, поэтому страница перезагружается без него
+        Consent::Declined if TRACKER.with(|tracker| tracker.borrow().loaded) => {
+            forget_attribution();
+            if let Some(window) = web_sys::window() {
+                let _ = window.location().reload();
+            }
+        }
+        Consent::Declined => forget_attribution(),
+    }
+}
+
+fn load() {
+    TRACKER.with(|tracker| {
+        let mut tracker = tracker.borrow_mut();
+        if tracker.loaded {
+            return;
+        }
+        match tracker.backend.load() {
+            Ok(()) => tracker.loaded = true,
+            Err(e) => gloo_console::error!("Failed to load analytics:", e),
+        }
+    });
+}
+
+/// Подключает внешний скрипт в `<head>`.
+fn append_script(src: &str, attributes: &[(&str, &str)]) -> Result<(), String> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("No document")?;
+    let script = document
+        .create_element("script")
+        .map_err(|e| format!("Failed to create script: {:?}", e))?;
+    let set = |name: &str, value: &str| {
+        script
+            .set_attribute(name, value)
+            .map_err(|e| format!("Failed to set {}: {:?}", name, e))
+    };
+    set("src", src)?;
+    set("async", "")?;
+    for (name, value) in attributes {
+        set(name, value)?;
+    }
+    document
+        .head()
+        .ok_or("No <head>")?
+        .append_child(&script)
+        .map_err(|e| format!("Failed to append script: {:?}", e))?;
+    Ok(())
+}
+
+/// Заглушка глобальной функции счётчика до загрузки его скрипта: вызовы копятся
+/// в массиве `window.<name>.<queue>`, скрипт разберёт их после загрузки. Собрана
+/// из замыкания, а не из строки кода: `new Function` запрещён политикой CSP.
+fn queue_stub(name: &str, queue: &str) -> Result<js_sys::Function, String> {
+    let window = js_sys::global();
+    let set = |target: &JsValue, key: &str, value: &JsValue| {
+        js_sys::Reflect::set(target, &key.into(), value)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to set {}: {:?}", key, e))
+    };
+
+    let existing = js_sys::Reflect::get(&window, &name.into())
+        .map_err(|e| format!("Failed to read {}: {:?}", name, e))?;
+    if let Some(function) = existing.dyn_ref::<js_sys::Function>() {
+        return Ok(function.clone());
+    }
+
+    let calls = js_sys::Array::new();
+    let pending = calls.clone();
+    // Счётчикам хватает четырёх аргументов; недостающие JS передаёт как undefined
+    let stub = Closure::<dyn Fn(JsValue, JsValue, JsValue, JsValue)>::new(
+        move |a: JsValue, b: JsValue, c: JsValue, d: JsValue| {
+            let args = [a, b, c, d];
+            let count = args
+                .iter()
+                .rposition(|arg| !arg.is_undefined())
+                .map_or(0, |i| i + 1);
+            pending.push(&args[..count].iter().collect::<js_sys::Array>());
+        },
+    )
+    .into_js_value();
+    set(&stub, queue, &calls)?;
+    set(&window, name, &stub)?;
+    Ok(stub.unchecked_into())
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// Цель аналитики. В Метрике имена целей заводятся как «JavaScript-событие»,
+/// в Plausible — как Custom event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Goal {
+    /// Кнопка первого экрана.
+    Cta {
+        text: String,
+    },
+    Social {
+        network: String,
+    },
+    Phone,
+    Email,
+    /// Переход в мессенджер с готовым текстом: калькулятор, сертификат, FAQ.
+    Messenger {
+        origin: &'static str,
+    },
+    PortfolioFilter {
+        tag: String,
+    },
+    ServiceFilter {
+        service_id: String,
+    },
+    /// Листание фото работы в галерее.
+    PortfolioSlide {
+        title: String,
+    },
+    /// «Построить маршрут» в картографическом сервисе.
+    Route {
+        service: &'static str,
+    },
+}
+
+impl Goal {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Goal::Cta { .. } => "cta_click",
+            Goal::Social { .. } => "social_click",
+            Goal::Phone => "phone_click",
+            Goal::Email => "email_click",
+            Goal::Messenger { .. } => "messenger_click",
+            Goal::PortfolioFilter { .. } => "portfolio_filter",
+            Goal::ServiceFilter { .. } => "service_filter",
+            Goal::PortfolioSlide { .. } => "portfolio_slide",
+            Goal::Route { .. } => "route_click",
+        }
+    }
+
+    fn params(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            Goal::Cta { text } => vec![("text", text)],
+            Goal::Social { network } => vec![("network", network)],
+            Goal::Phone | Goal::Email => Vec::new(),
+            Goal::Messenger { origin } => vec![("origin", origin)],
+            Goal::PortfolioFilter { tag } => vec![("tag", tag)],
+            Goal::ServiceFilter { service_id } => vec![("service_id", service_id)],
+            Goal::PortfolioSlide { title } => vec![("title", title)],
+            Goal::Route { service } => vec![("service", service)],
+        }
+    }
+}
+
+pub fn reach_goal(goal: &Goal) {
+    TRACKER.with(|tracker| {
+        let tracker = tracker.borrow();
+        if tracker.loaded {
+            tracker.backend.reach_goal(goal);
+        }
+    });
+}
+
+/// Параметры цели в виде объекта JS.
+fn goal_params(goal: &Goal) -> wasm_bindgen::JsValue {
+    let params = js_sys::Object::new();
+    for (key, value) in goal.params() {
+        let _ = js_sys::Reflect::set(&params, &key.into(), &value.into());
+    }
+    params.into()
+}
+
+/// Обработчик клика, отправляющий цель.
+pub fn on_click<E: 'static>(goal: Goal) -> Callback<E> {
+    Callback::from(move |_| reach_goal(&goal))
+}
+
+/// UTM-метки рекламной ссылки, с которой пришёл посетитель.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Attribution {
+    pub source: String,
+    #[serde(default)]
+    pub medium: Option<String>,
+    #[serde(default)]
+    pub campaign: Option<String>,
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub term: Option<String>,
+}
+
+impl Attribution {
+    fn from_url() -> Option<Self> {
+        Self::from_params(url_query::get)
+    }
+
+    /// Метки из параметров ссылки; без `utm_source` меток нет, пустые не считаются.
+    fn from_params(get: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let param = |name: &str| get(name).filter(|value| !value.trim().is_empty());
+        Some(Self {
+            source: param("utm_source")?,
+            medium: param("utm_medium"),
+            campaign: param("utm_campaign"),
+            content: param("utm_content"),
+            term: param("utm_term"),
+        })
+    }
+
+    /// Короткая метка для текста заявки: `instagram/stories/spring`.
+    pub fn tag(&self) -> String {
+        std::iter::once(self.source.as_str())
+            .chain(self.medium.as_deref())
+            .chain(self.campaign.as_deref())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Запоминает UTM-метки из адреса страницы. До согласия посетителя метки живут
+/// только в памяти; с согласием они сохраняются в браузере, и без меток в адресе
+/// действуют метки прошлого визита: клиент часто возвращается на сайт позже.
+pub fn capture_attribution() {
+    let attribution = match (Attribution::from_url(), consent()) {
+        (_, Some(Consent::Declined)) => {
+            forget_attribution();
+            return;
+        }
+        (Some(attribution), _) => Some(attribution),
+        (None, Some(Consent::Accepted)) => storage()
+            .and_then(|storage| storage.get_item(ATTRIBUTION_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        (None, None) => None,
+    };
+    ATTRIBUTION.with(|current| *current.borrow_mut() = attribution);
+    if consent() == Some(Consent::Accepted) {
+        persist_attribution();
+    }
+}
+
+fn persist_attribution() {
+    let json = ATTRIBUTION.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .and_then(|attribution| serde_json::to_string(attribution).ok())
+    });
+    if let (Some(storage), Some(json)) = (storage(), json) {
+        let _ = storage.set_item(ATTRIBUTION_KEY, &json);
+    }
+}
+
+fn forget_attribution() {
+    ATTRIBUTION.with(|current| *current.borrow_mut() = None);
+    if let Some(storage) = storage() {
+        let _ = storage.remove_item(ATTRIBUTION_KEY);
+    }
+}
+
+/// Метка источника для текста заявки в мессенджер; только с согласия посетителя.
+pub fn source_tag() -> Option<String> {
+    if consent() != Some(Consent::Accepted) {
+        return None;
+    }
+    ATTRIBUTION.with(|current| current.borrow().as_ref().map(Attribution::tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribution(params: &[(&str, &str)]) -> Option<Attribution> {
+        Attribution::from_params(|name| {
+            params
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn tag_joins_source_medium_and_campaign() {
+        let full = attribution(&[
+            ("utm_source", "instagram"),
+            ("utm_medium", "stories"),
+            ("utm_campaign", "spring"),
+            ("utm_content", "video"),
+            ("utm_term", "брови"),
+        ])
+        .unwrap();
+        assert_eq!(full.tag(), "instagram/stories/spring");
+        assert_eq!(full.content.as_deref(), Some("video"));
+
+        let source_only = attribution(&[("utm_source", "vk")]).unwrap();
+        assert_eq!(source_only.tag(), "vk");
+
+        let no_medium = attribution(&[("utm_source", "vk"), ("utm_campaign", "may")]).unwrap();
+        assert_eq!(no_medium.tag(), "vk/may");
+    }
+
+    #[test]
+    fn source_is_required_and_empty_params_are_ignored() {
+        assert_eq!(attribution(&[("utm_medium", "cpc")]), None);
+        assert_eq!(attribution(&[("utm_source", "  ")]), None);
+
+        let with_empty = attribution(&[("utm_source", "yandex"), ("utm_medium", "")]).unwrap();
+        assert_eq!(with_empty.medium, None);
+        assert_eq!(with_empty.tag(), "yandex");
+    }
+
+    #[test]
+    fn stored_attribution_round_trips() {
+        let stored = attribution(&[("utm_source", "vk"), ("utm_medium", "post")]).unwrap();
+        let json = serde_json::to_string(&stored).unwrap();
+        assert_eq!(serde_json::from_str::<Attribution>(&json).unwrap(), stored);
+        assert_eq!(
+            serde_json::from_str::<Attribution>(r#"{"source":"vk"}"#).unwrap(),
+            attribution(&[("utm_source", "vk")]).unwrap()
+        );
+    }
+}
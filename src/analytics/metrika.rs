@@ -0,0 +1,50 @@
+use super::{Analytics, Goal, append_script, goal_params, queue_stub};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Глобальная функция счётчика; её может не быть, если Метрику заблокировали.
+    #[wasm_bindgen(catch)]
+    fn ym(counter: u32, method: &str, target: &JsValue, params: &JsValue) -> Result<(), JsValue>;
+}
+
+pub struct Metrika {
+    pub counter_id: u32,
+    pub webvisor: bool,
+}
+
+impl Analytics for Metrika {
+    fn load(&self) -> Result<(), String> {
+        // Очередь вызовов `ym` до загрузки tag.js, как в официальном коде счётчика
+        let stub = queue_stub("ym", "a")?;
+        js_sys::Reflect::set(&stub, &"l".into(), &js_sys::Date::now().into())
+            .map_err(|e| format!("Failed to init Metrika: {:?}", e))?;
+
+        let options = js_sys::Object::new();
+        for (key, value) in [
+            ("ssr", true),
+            ("clickmap", true),
+            ("trackLinks", true),
+            ("accurateTrackBounce", true),
+            ("webvisor", self.webvisor),
+        ] {
+            let _ = js_sys::Reflect::set(&options, &key.into(), &value.into());
+        }
+        ym(self.counter_id, "init", &options, &JsValue::UNDEFINED)
+            .map_err(|e| format!("Failed to init Metrika: {:?}", e))?;
+
+        append_script(
+            &format!("https://mc.yandex.ru/metrika/tag.js?id={}", self.counter_id),
+            &[],
+        )
+    }
+
+    fn reach_goal(&self, goal: &Goal) {
+        let _ = ym(
+            self.counter_id,
+            "reachGoal",
+            &goal.name().into(),
+            &goal_params(goal),
+        );
+    }
+}
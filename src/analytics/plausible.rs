@@ -0,0 +1,30 @@
+use super::{Analytics, Goal, append_script, goal_params, queue_stub};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn plausible(event: &str, options: &JsValue) -> Result<(), JsValue>;
+}
+
+pub struct Plausible {
+    pub domain: String,
+    pub script: String,
+}
+
+impl Analytics for Plausible {
+    fn load(&self) -> Result<(), String> {
+        // Очередь событий до загрузки скрипта, как в документации Plausible
+        queue_stub("plausible", "q")?;
+        append_script(
+            &self.script,
+            &[("data-domain", &self.domain), ("defer", "")],
+        )
+    }
+
+    fn reach_goal(&self, goal: &Goal) {
+        let options = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&options, &"props".into(), &goal_params(goal));
+        let _ = plausible(goal.name(), &options);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::analytics;
+use crate::data::ServiceItem;
+use crate::pricing::{Quote, format_minutes, format_rub};
+use crate::promo;
+use crate::url_query;
+
+/// Текст заявки на запись, который клиент отправляет в мессенджер.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookingMessage {
+    lines: Vec<String>,
+}
+
+impl Default for BookingMessage {
+    fn default() -> Self {
+        Self::new("Здравствуйте! Хочу записаться.")
+    }
+}
+
+impl BookingMessage {
+    pub fn new(greeting: impl Into<String>) -> Self {
+        Self {
+            lines: vec![greeting.into()],
+        }
+    }
+
+    /// Заявка с выбранными в калькуляторе услугами и расчётом.
+    pub fn with_services(items: &[&ServiceItem], quote: &Quote) -> Self {
+        let mut message = Self::default();
+        if items.is_empty() {
+            return message;
+        }
+
+        message.push("Услуги:");
+        for item in items {
+            message.push(format!("— {} ({})", item.name, item.price));
+        }
+        for combo in &quote.combos {
+            message.push(format!(
+                "Комплекс «{}»: −{}",
+                combo.name,
+                format_rub(combo.saving)
+            ));
+        }
+
+        let prefix = if quote.approximate { "≈ " } else { "" };
+        message.push(format!("Итого: {}{}", prefix, format_rub(quote.total)));
+        if quote.minutes > 0 {
+            message.push(format!(
+                "Время: {}{}",
+                prefix,
+                format_minutes(quote.minutes)
+            ));
+        }
+        message
+    }
+
+    pub fn push(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    /// Ссылка wa.me с подставленным текстом заявки; код приглашения и рекламный
+    /// источник, с которыми пришёл клиент, добавляются в конец текста.
+    pub fn whatsapp_url(&self, whatsapp: &str) -> String {
+        self.text_url(whatsapp, &extras())
+    }
+
+    /// Адрес кнопки записи. Ссылки WhatsApp и Telegram получают текст заявки.
+    /// Сервис онлайн-записи текст не принимает, поэтому его ссылка получает код
+    /// приглашения параметром `promo`. Телефон и ссылки на разделы не меняются.
+    pub fn link(&self, href: &str) -> String {
+        self.link_with(href, promo::active().as_deref(), &extras())
+    }
+
+    fn text_with(&self, extras: &[String]) -> String {
+        self.lines
+            .iter()
+            .chain(extras)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn text_url(&self, messenger: &str, extras: &[String]) -> String {
+        with_param(messenger, "text", &self.text_with(extras))
+    }
+
+    fn link_with(&self, href: &str, promo: Option<&str>, extras: &[String]) -> String {
+        if is_messenger(href) {
+            self.text_url(href, extras)
+        } else if let Some(code) = promo.filter(|_| is_online_booking(href)) {
+            with_param(href, PROMO_PARAM, code)
+        } else {
+            href.to_string()
+        }
+    }
+}
+
+/// Параметр, в котором сервис онлайн-записи получает код приглашения.
+const PROMO_PARAM: &str = "promo";
+
+/// Код приглашения, который показывается рядом с кнопкой онлайн-записи: сервис
+/// записи может не сохранить параметр, и клиент назовёт код сам.
+pub fn online_booking_code(href: &str) -> Option<String> {
+    promo::active().filter(|_| is_online_booking(href))
+}
+
+/// Строки о коде приглашения и рекламном источнике для конца заявки.
+fn extras() -> Vec<String> {
+    let promo = promo::active().map(|code| format!("Код приглашения: {}", code));
+    let source = analytics::source_tag().map(|tag| format!("Источник: {}", tag));
+    promo.into_iter().chain(source).collect()
+}
+
+/// Ссылка на WhatsApp или Telegram, куда можно подставить текст заявки.
+pub fn is_messenger(href: &str) -> bool {
+    [
+        "https://wa.me/",
+        "https://api.whatsapp.com/",
+        "whatsapp://",
+        "https://t.me/",
+        "https://telegram.me/",
+        "tg://",
+    ]
+    .iter()
+    .any(|prefix| href.starts_with(prefix))
+}
+
+/// Внешняя ссылка, кроме мессенджеров: в кнопках записи это сервис онлайн-записи.
+fn is_online_booking(href: &str) -> bool {
+    (href.starts_with("https://") || href.starts_with("http://")) && !is_messenger(href)
+}
+
+fn with_param(url: &str, name: &str, value: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!(
+        "{}{}{}={}",
+        url,
+        separator,
+        name,
+        url_query::encode_component(value)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONLINE_BOOKING: &str = "https://mst.link/maslova_inna1";
+    const TELEGRAM: &str = "https://t.me/innamaslinna";
+    const WHATSAPP: &str = "https://wa.me/79618519801";
+
+    fn extras() -> Vec<String> {
+        vec![
+            "Код приглашения: ANNA".to_string(),
+            "Источник: instagram/stories".to_string(),
+        ]
+    }
+
+    #[test]
+    fn appends_promo_code_and_source_to_the_message() {
+        let message = BookingMessage::default();
+        assert_eq!(
+            message.text_with(&extras()),
+            "Здравствуйте! Хочу записаться.\nКод приглашения: ANNA\nИсточник: instagram/stories"
+        );
+        assert_eq!(message.text_with(&[]), "Здравствуйте! Хочу записаться.");
+    }
+
+    #[test]
+    fn messenger_links_carry_the_message() {
+        let message = BookingMessage::new("Привет");
+        assert_eq!(
+            message.link_with(WHATSAPP, None, &[]),
+            "https://wa.me/79618519801?text=%D0%9F%D1%80%D0%B8%D0%B2%D0%B5%D1%82"
+        );
+        assert!(
+            message
+                .link_with("https://wa.me/79618519801?lang=ru", None, &[])
+                .starts_with("https://wa.me/79618519801?lang=ru&text=")
+        );
+
+        let telegram = message.link_with(TELEGRAM, Some("ANNA"), &extras());
+        assert!(telegram.starts_with("https://t.me/innamaslinna?text="));
+        assert!(telegram.contains(&url_query::encode_component("Код приглашения: ANNA")));
+    }
+
+    #[test]
+    fn online_booking_link_gets_the_code_as_a_parameter() {
+        let message = BookingMessage::default();
+        assert_eq!(message.link_with(ONLINE_BOOKING, None, &[]), ONLINE_BOOKING);
+        assert_eq!(
+            message.link_with(ONLINE_BOOKING, Some("ANNA"), &extras()),
+            "https://mst.link/maslova_inna1?promo=ANNA"
+        );
+        assert_eq!(
+            message.link_with("https://example.ru/book?lang=ru", Some("ДРУГ"), &extras()),
+            "https://example.ru/book?lang=ru&promo=%D0%94%D0%A0%D0%A3%D0%93"
+        );
+    }
+
+    #[test]
+    fn other_links_are_unchanged() {
+        let message = BookingMessage::default();
+        for href in ["tel:+79618519801", "#services"] {
+            assert_eq!(message.link_with(href, Some("ANNA"), &extras()), href);
+        }
+    }
+}
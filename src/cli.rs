@@ -0,0 +1,77 @@
+pub mod args;
+pub mod certificates;
+pub mod import_reviews;
+pub mod price_sheet;
+pub mod prices;
+pub mod pwa;
+pub mod validate;
+
+const USAGE: &str = "\
+Использование: cargo run -- <команда> [параметры]
+
+Команды:
+  import-reviews <файлы...>   Импорт отзывов из выгрузок Яндекс Карт, 2ГИС и VK
+      --content <путь>        content.yaml для проверки дублей (static/content.yaml)
+      --output <путь>         Куда записать отзывы (static/testimonials.yaml)
+      --platform <имя>        yandex, 2gis или vk (по умолчанию — по имени файла)
+      --service <текст>       Подпись услуги для отзывов без неё
+
+  prices export [файл.csv]    Выгрузка прайса в CSV (prices.csv)
+      --content <путь>        content.yaml с услугами (static/content.yaml)
+      --delimiter <символ>    Разделитель колонок: , ; или tab (,)
+
+  prices import <файл.csv>    Загрузка прайса из CSV с отчётом об изменениях
+      --content <путь>        content.yaml с услугами (static/content.yaml)
+      --output <путь>         Куда записать услуги (файл, подключённый как services)
+      --dry-run               Только показать отчёт, ничего не записывать
+
+  prices sheet [файл.html]    Прайс для печати в PDF одним HTML-файлом (prices.html)
+      --content <путь>        content.yaml с услугами (static/content.yaml)
+      --url <адрес>           Адрес сайта для QR-кода (info.site_url)
+
+  certificates add <код>      Зарегистрировать проданный подарочный сертификат
+      --amount <рублей>       Номинал сертификата
+      --service <id>          Или услуга, на которую он выписан
+      --months <число>        Срок действия (gift_certificates.valid_months)
+      --codes <путь>          Файл сертификатов (gift_certificates.codes)
+      --content <путь>        content.yaml (static/content.yaml)
+
+  certificates redeem <код>   Отметить сертификат использованным
+  certificates check <код>    Проверить код сертификата
+
+  validate                    Проверить контент: ссылки на услуги, дубли, alt у фото
+      --content <путь>        content.yaml (static/content.yaml)
+
+  pwa                         Манифест, иконка и список офлайн-файлов в static/pwa
+      --content <путь>        content.yaml (static/content.yaml)
+      --check                 Только проверить, что файлы не устарели
+";
+
+/// Точка входа консольных команд; возвращает код завершения процесса.
+pub fn run(args: Vec<String>) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        eprint!("{}", USAGE);
+        return 2;
+    };
+
+    let result = match command.as_str() {
+        "import-reviews" => import_reviews::run(rest),
+        "prices" => prices::run(rest),
+        "certificates" => certificates::run(rest),
+        "validate" => validate::run(rest),
+        "pwa" => pwa::run(rest),
+        "help" | "--help" | "-h" => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::{HashMap, HashSet};
+
+/// Разобранные аргументы команды: позиционные значения, `--ключ значение`
+/// и флаги без значения.
+#[derive(Debug, Default)]
+pub struct Args {
+    pub positional: Vec<String>,
+    options: HashMap<String, String>,
+    flags: HashSet<String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String], known: &[&str], known_flags: &[&str]) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            match arg.strip_prefix("--") {
+                Some(name) if known.contains(&name) => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| format!("Option --{} requires a value", name))?;
+                    parsed.options.insert(name.to_string(), value.clone());
+                }
+                Some(name) if known_flags.contains(&name) => {
+                    parsed.flags.insert(name.to_string());
+                }
+                Some(name) => return Err(format!("Unknown option: --{}", name)),
+                None => parsed.positional.push(arg.clone()),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn get_or<'a>(&'a self, name: &str, default: &'a str) -> &'a str {
+        self.get(name).unwrap_or(default)
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+}
@@ -0,0 +1,184 @@
+use crate::cli::args::Args;
+use crate::content_loader;
+use crate::data::{CertificateRegistry, Content, IssuedCertificate};
+use crate::date::CalendarDate;
+use crate::gift_certificate::{self, CertificateStatus, CertificateValue};
+use std::fs;
+use std::hash::{BuildHasher, RandomState};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const REGISTRY_HEADER: &str = "\
+# Выданные подарочные сертификаты. Файл публичный, поэтому вместо кодов
+# хранятся их хэши PBKDF2 с солью. Правится командами:
+#   cargo run -- certificates add YI-XXXX-XXXX-XXXX-XXXX --amount 3000
+#   cargo run -- certificates redeem YI-XXXX-XXXX-XXXX-XXXX
+
+";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (command, rest) = args
+        .split_first()
+        .ok_or("Expected `certificates add`, `certificates redeem` or `certificates check`")?;
+    let args = Args::parse(
+        rest,
+        &["content", "codes", "amount", "service", "months"],
+        &[],
+    )?;
+    let [code] = args.positional.as_slice() else {
+        return Err("Expected exactly one certificate code".to_string());
+    };
+
+    let content = content_loader::load_content_file(args.get_or("content", "static/content.yaml"))?;
+    let codes_path = registry_path(&args, &content)?;
+    let mut registry = read_registry(&codes_path)?;
+
+    match command.as_str() {
+        "add" => add(&args, &content, &mut registry, code)?,
+        "redeem" => redeem(&mut registry, code)?,
+        "check" => {
+            print_status(&registry.check(code, today()));
+            return Ok(());
+        }
+        other => return Err(format!("Unknown certificates command: {}", other)),
+    }
+
+    let yaml = serde_yaml::to_string(&registry)
+        .map_err(|e| format!("Failed to serialize certificates: {}", e))?;
+    fs::write(&codes_path, format!("{}{}", REGISTRY_HEADER, yaml))
+        .map_err(|e| format!("Failed to write {}: {}", codes_path, e))?;
+    println!("Written to {}", codes_path);
+    Ok(())
+}
+
+fn add(
+    args: &Args,
+    content: &Content,
+    registry: &mut CertificateRegistry,
+    code: &str,
+) -> Result<(), String> {
+    let prefix = content
+        .gift_certificates
+        .as_ref()
+        .map_or("YI", |section| section.code_prefix.as_str());
+    if gift_certificate::random_symbols(code, prefix) < gift_certificate::CODE_SYMBOLS {
+        return Err(format!(
+            "Certificate code must be {} followed by {} random symbols, like the site generates",
+            prefix,
+            gift_certificate::CODE_SYMBOLS
+        ));
+    }
+    if registry.salt.is_empty() {
+        registry.salt = new_salt();
+    }
+    if registry.find(code).is_some() {
+        return Err(format!("Certificate {} is already registered", code));
+    }
+
+    let value = match (args.get("amount"), args.get("service")) {
+        (Some(amount), None) => CertificateValue::Amount(
+            amount
+                .parse()
+                .map_err(|_| format!("Invalid amount: {}", amount))?,
+        ),
+        (None, Some(id)) if content.services.find(id).is_some() => {
+            CertificateValue::Service(id.to_string())
+        }
+        (None, Some(id)) => return Err(format!("Unknown service id: {}", id)),
+        _ => return Err("Pass either --amount <рублей> or --service <id>".to_string()),
+    };
+    let months = match args.get("months") {
+        Some(months) => months
+            .parse()
+            .map_err(|_| format!("Invalid number of months: {}", months))?,
+        None => content
+            .gift_certificates
+            .as_ref()
+            .map_or(6, |section| section.valid_months),
+    };
+
+    let certificate = IssuedCertificate {
+        code_hash: registry.hash(code),
+        title: value.title(&content.services),
+        valid_until: today().add_months(months),
+        redeemed: false,
+    };
+    println!(
+        "Registered {}: {}, valid until {}",
+        code, certificate.title, certificate.valid_until
+    );
+    registry.certificates.push(certificate);
+    Ok(())
+}
+
+fn redeem(registry: &mut CertificateRegistry, code: &str) -> Result<(), String> {
+    let hash = registry.hash(code);
+    let certificate = registry
+        .certificates
+        .iter_mut()
+        .find(|certificate| certificate.code_hash == hash)
+        .ok_or_else(|| format!("Certificate {} is not registered", code))?;
+    if certificate.redeemed {
+        return Err(format!("Certificate {} is already redeemed", code));
+    }
+    certificate.redeemed = true;
+    println!("Redeemed {}: {}", code, certificate.title);
+    Ok(())
+}
+
+fn print_status(status: &CertificateStatus) {
+    match status {
+        CertificateStatus::Valid(certificate) => println!(
+            "Valid: {}, until {}",
+            certificate.title, certificate.valid_until
+        ),
+        CertificateStatus::Expired(certificate) => println!(
+            "Expired on {}: {}",
+            certificate.valid_until, certificate.title
+        ),
+        CertificateStatus::Redeemed(certificate) => {
+            println!("Already redeemed: {}", certificate.title)
+        }
+        CertificateStatus::NotFound => println!("Not found"),
+    }
+}
+
+/// Путь к файлу сертификатов: `--codes` или `gift_certificates.codes` из content.yaml.
+fn registry_path(args: &Args, content: &Content) -> Result<String, String> {
+    if let Some(path) = args.get("codes") {
+        return Ok(path.to_string());
+    }
+    content
+        .gift_certificates
+        .as_ref()
+        .and_then(|section| section.codes.as_deref())
+        .map(|path| path.trim_start_matches('/').to_string())
+        .ok_or_else(|| "No gift_certificates.codes in content, pass --codes <путь>".to_string())
+}
+
+fn read_registry(path: &str) -> Result<CertificateRegistry, String> {
+    if !Path::new(path).exists() {
+        return Ok(CertificateRegistry::default());
+    }
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    serde_yaml::from_str(&text).map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+/// Соль не секретна, ей достаточно быть своей у каждого сайта.
+fn new_salt() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos());
+    let state = RandomState::new();
+    [state.hash_one(nanos), state.hash_one(nanos.rotate_left(64))]
+        .iter()
+        .map(|part| format!("{:016x}", part))
+        .collect()
+}
+
+fn today() -> CalendarDate {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64);
+    CalendarDate::from_unix(seconds)
+}
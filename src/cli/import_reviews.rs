@@ -0,0 +1,568 @@
+use crate::cli::args::Args;
+use crate::content_loader;
+use crate::data::{ImportedTestimonials, Testimonial};
+use crate::date::CalendarDate;
+use crate::rating::Rating;
+use crate::text;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+const NAME_FIELDS: &[&str] = &[
+    "author.name",
+    "user.name",
+    "author",
+    "name",
+    "user_name",
+    "имя",
+];
+const RATING_FIELDS: &[&str] = &["rating", "rating.value", "stars", "score", "оценка"];
+const TEXT_FIELDS: &[&str] = &["text", "comment", "body", "review", "отзыв"];
+const DATE_FIELDS: &[&str] = &[
+    "updatedTime",
+    "date_created",
+    "date_edited",
+    "date",
+    "created_at",
+    "time",
+    "дата",
+];
+const SERVICE_FIELDS: &[&str] = &["service", "услуга"];
+
+/// Число символов начала отзыва, по которым вместе с именем ищутся дубли.
+const DEDUP_PREFIX_CHARS: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Platform {
+    Yandex,
+    TwoGis,
+    Vk,
+}
+
+impl Platform {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "yandex" | "яндекс" => Ok(Platform::Yandex),
+            "2gis" | "2гис" => Ok(Platform::TwoGis),
+            "vk" | "вк" => Ok(Platform::Vk),
+            other => Err(format!("Unknown platform: {}", other)),
+        }
+    }
+
+    fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.contains("yandex") || name.contains("яндекс") {
+            Some(Platform::Yandex)
+        } else if name.contains("2gis") || name.contains("2гис") {
+            Some(Platform::TwoGis)
+        } else if name.contains("vk") || name.contains("вк") {
+            Some(Platform::Vk)
+        } else {
+            None
+        }
+    }
+}
+
+/// Отзыв в том виде, в каком он найден в выгрузке, до проверки полей.
+#[derive(Debug, Default)]
+struct ReviewRecord {
+    name: Option<String>,
+    rating: Option<f32>,
+    text: Option<String>,
+    date: Option<CalendarDate>,
+    service: Option<String>,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["content", "output", "platform", "service"], &[])?;
+    if args.positional.is_empty() {
+        return Err("No review export files given".to_string());
+    }
+
+    let content_path = args.get_or("content", "static/content.yaml");
+    let output_path = args.get_or("output", "static/testimonials.yaml");
+
+    let content = content_loader::load_content_file(content_path)?;
+
+    let mut imported = if Path::new(output_path).exists() {
+        let text = fs::read_to_string(output_path)
+            .map_err(|e| format!("Failed to read {}: {}", output_path, e))?;
+        serde_yaml::from_str::<ImportedTestimonials>(&text)
+            .map_err(|e| format!("Failed to parse {}: {}", output_path, e))?
+    } else {
+        ImportedTestimonials::default()
+    };
+
+    let mut seen = content
+        .testimonials
+        .items
+        .iter()
+        .chain(imported.items.iter())
+        .map(dedup_key)
+        .collect::<HashSet<_>>();
+
+    let mut added = 0;
+    let mut duplicates = 0;
+    for file in &args.positional {
+        let path = Path::new(file);
+        let platform = match args.get("platform") {
+            Some(name) => Platform::parse(name)?,
+            None => Platform::detect(path).ok_or_else(|| {
+                format!(
+                    "Cannot detect platform for {}, pass --platform yandex|2gis|vk",
+                    file
+                )
+            })?,
+        };
+        // Площадка — не услуга: без --service поле остаётся пустым до ручной разметки
+        let service = args.get_or("service", "");
+
+        for record in read_records(path, platform)? {
+            let testimonial = match into_testimonial(record, service) {
+                Ok(testimonial) => testimonial,
+                Err(e) => {
+                    eprintln!("Skipped review in {}: {}", file, e);
+                    continue;
+                }
+            };
+
+            if seen.insert(dedup_key(&testimonial)) {
+                imported.items.push(testimonial);
+                added += 1;
+            } else {
+                duplicates += 1;
+            }
+        }
+    }
+
+    imported.items.sort_by_key(|t| std::cmp::Reverse(t.date));
+
+    let yaml = serde_yaml::to_string(&imported)
+        .map_err(|e| format!("Failed to serialize testimonials: {}", e))?;
+    fs::write(
+        output_path,
+        format!(
+            "# Отзывы, импортированные командой `cargo run -- import-reviews`.\n\
+             # Подключаются через testimonials.imported в content.yaml.\n{}",
+            yaml
+        ),
+    )
+    .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+
+    println!(
+        "Added {} reviews, skipped {} duplicates, written to {}",
+        added, duplicates, output_path
+    );
+    Ok(())
+}
+
+fn into_testimonial(record: ReviewRecord, default_service: &str) -> Result<Testimonial, String> {
+    let name = record
+        .name
+        .filter(|n| !n.trim().is_empty())
+        .ok_or("missing author name")?;
+    let text = record
+        .text
+        .filter(|t| !t.trim().is_empty())
+        .ok_or_else(|| format!("review from {} has no text", name))?;
+    let rating = record
+        .rating
+        .ok_or_else(|| format!("review from {} has no rating", name))
+        .and_then(Rating::try_from)?;
+    let date = record
+        .date
+        .ok_or_else(|| format!("review from {} has no date", name))?;
+
+    Ok(Testimonial {
+        avatar: text::initials(&name),
+        name: name.trim().to_string(),
+        rating,
+        text: text.trim().to_string(),
+        date,
+        service: record
+            .service
+            .unwrap_or_else(|| default_service.to_string()),
+        service_ids: Vec::new(),
+    })
+}
+
+fn dedup_key(testimonial: &Testimonial) -> (String, String) {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let text = normalize(&testimonial.text)
+        .chars()
+        .take(DEDUP_PREFIX_CHARS)
+        .collect();
+    (normalize(&testimonial.name), text)
+}
+
+fn read_records(path: &Path, platform: Platform) -> Result<Vec<ReviewRecord>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "json" => read_json(&content, platform),
+        "csv" => read_csv(&content),
+        "html" | "htm" => Ok(read_html(&content)),
+        other => Err(format!(
+            "Unsupported export format \"{}\" for {}, expected json, csv or html",
+            other,
+            path.display()
+        )),
+    }
+}
+
+fn read_json(content: &str, platform: Platform) -> Result<Vec<ReviewRecord>, String> {
+    let root: Value =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    // VK отдаёт авторов отдельным списком profiles, а в отзыве только from_id
+    let vk_profiles = if platform == Platform::Vk {
+        vk_profiles(&root)
+    } else {
+        HashMap::new()
+    };
+
+    let mut records = Vec::new();
+    collect_json_reviews(&root, &vk_profiles, &mut records);
+    Ok(records)
+}
+
+fn collect_json_reviews(
+    value: &Value,
+    vk_profiles: &HashMap<i64, String>,
+    records: &mut Vec<ReviewRecord>,
+) {
+    match value {
+        Value::Array(items) if items.iter().any(looks_like_review) => {
+            records.extend(
+                items
+                    .iter()
+                    .filter(|item| looks_like_review(item))
+                    .map(|item| json_record(item, vk_profiles)),
+            );
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_json_reviews(item, vk_profiles, records)),
+        Value::Object(map) => map
+            .values()
+            .for_each(|item| collect_json_reviews(item, vk_profiles, records)),
+        _ => {}
+    }
+}
+
+fn looks_like_review(value: &Value) -> bool {
+    value.is_object() && TEXT_FIELDS.iter().any(|f| json_str(value, f).is_some())
+}
+
+fn json_record(item: &Value, vk_profiles: &HashMap<i64, String>) -> ReviewRecord {
+    let name = NAME_FIELDS
+        .iter()
+        .find_map(|f| json_str(item, f))
+        .or_else(|| {
+            item.get("from_id")
+                .and_then(Value::as_i64)
+                .and_then(|id| vk_profiles.get(&id).cloned())
+        });
+
+    ReviewRecord {
+        name,
+        // Следующий ключ пробуется, если значение под ключом не разобралось:
+        // `rating` может оказаться объектом, тогда оценка лежит в `rating.value`
+        rating: RATING_FIELDS
+            .iter()
+            .find_map(|f| json_path(item, f).and_then(json_rating)),
+        text: TEXT_FIELDS.iter().find_map(|f| json_str(item, f)),
+        date: DATE_FIELDS
+            .iter()
+            .find_map(|f| json_path(item, f).and_then(json_date)),
+        service: SERVICE_FIELDS.iter().find_map(|f| json_str(item, f)),
+    }
+}
+
+fn vk_profiles(root: &Value) -> HashMap<i64, String> {
+    let profiles = root
+        .pointer("/response/profiles")
+        .or_else(|| root.get("profiles"))
+        .and_then(Value::as_array);
+
+    profiles
+        .into_iter()
+        .flatten()
+        .filter_map(|p| {
+            let id = p.get("id")?.as_i64()?;
+            let first = p.get("first_name")?.as_str()?;
+            let last = p.get("last_name").and_then(Value::as_str).unwrap_or("");
+            Some((id, format!("{} {}", first, last).trim().to_string()))
+        })
+        .collect()
+}
+
+fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |v, key| v.get(key))
+}
+
+fn json_rating(value: &Value) -> Option<f32> {
+    value
+        .as_f64()
+        .map(|r| r as f32)
+        .or_else(|| value.as_str().and_then(parse_rating))
+}
+
+fn json_date(value: &Value) -> Option<CalendarDate> {
+    match value {
+        Value::Number(n) => n.as_i64().map(unix_date),
+        Value::String(s) => CalendarDate::parse(s).ok(),
+        _ => None,
+    }
+}
+
+fn json_str(value: &Value, path: &str) -> Option<String> {
+    json_path(value, path)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Unix-время в секундах или миллисекундах.
+fn unix_date(timestamp: i64) -> CalendarDate {
+    if timestamp > 100_000_000_000 {
+        CalendarDate::from_unix(timestamp / 1000)
+    } else {
+        CalendarDate::from_unix(timestamp)
+    }
+}
+
+fn parse_rating(value: &str) -> Option<f32> {
+    value.trim().replace(',', ".").parse().ok()
+}
+
+fn read_csv(content: &str) -> Result<Vec<ReviewRecord>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let column = |fields: &[&str]| {
+        fields
+            .iter()
+            .find_map(|f| headers.iter().position(|h| h == &f.to_lowercase()))
+    };
+    let name_col = column(NAME_FIELDS);
+    let rating_col = column(RATING_FIELDS);
+    let text_col = column(TEXT_FIELDS);
+    let date_col = column(DATE_FIELDS);
+    let service_col = column(SERVICE_FIELDS);
+
+    reader
+        .records()
+        .map(|row| {
+            let row = row.map_err(|e| format!("Failed to read CSV row: {}", e))?;
+            let cell = |col: Option<usize>| {
+                col.and_then(|i| row.get(i))
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+            };
+
+            Ok(ReviewRecord {
+                name: cell(name_col),
+                rating: cell(rating_col).and_then(|r| parse_rating(&r)),
+                text: cell(text_col),
+                date: cell(date_col).and_then(|d| {
+                    CalendarDate::parse(&d)
+                        .ok()
+                        .or_else(|| d.parse().ok().map(unix_date))
+                }),
+                service: cell(service_col),
+            })
+        })
+        .collect()
+}
+
+/// Сохранённая страница с разметкой schema.org Review (её используют Яндекс Карты и 2ГИС).
+fn read_html(content: &str) -> Vec<ReviewRecord> {
+    use scraper::{ElementRef, Html, Selector};
+
+    let selector = |s: &str| Selector::parse(s).ok();
+    let (Some(review), Some(author_name), Some(author), Some(rating), Some(body), Some(published)) = (
+        selector(r#"[itemprop="review"]"#),
+        selector(r#"[itemprop="author"] [itemprop="name"]"#),
+        selector(r#"[itemprop="author"]"#),
+        selector(r#"[itemprop="ratingValue"]"#),
+        selector(r#"[itemprop="reviewBody"], [itemprop="description"]"#),
+        selector(r#"[itemprop="datePublished"]"#),
+    ) else {
+        return Vec::new();
+    };
+
+    // Значение microdata: атрибут content/datetime или текст элемента
+    let value = |element: ElementRef| {
+        element
+            .value()
+            .attr("content")
+            .or_else(|| element.value().attr("datetime"))
+            .map(str::to_string)
+            .unwrap_or_else(|| element.text().collect::<String>())
+            .trim()
+            .to_string()
+    };
+
+    Html::parse_document(content)
+        .select(&review)
+        .map(|node| ReviewRecord {
+            name: node
+                .select(&author_name)
+                .next()
+                .or_else(|| node.select(&author).next())
+                .map(value),
+            rating: node
+                .select(&rating)
+                .next()
+                .map(value)
+                .and_then(|r| parse_rating(&r)),
+            text: node.select(&body).next().map(value),
+            date: node
+                .select(&published)
+                .next()
+                .map(value)
+                .and_then(|d| CalendarDate::parse(&d).ok()),
+            service: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+        CalendarDate::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn reads_csv_with_dotted_dates_and_decimal_comma() {
+        let csv = "Имя,Оценка,Отзыв,Дата\nОльга,\"4,0\",Всё понравилось,12.05.2024\n";
+        let records = read_csv(csv).unwrap();
+        assert_eq!(records.len(), 1);
+
+        let testimonial = into_testimonial(records.into_iter().next().unwrap(), "").unwrap();
+        assert_eq!(testimonial.name, "Ольга");
+        assert_eq!(testimonial.rating, Rating::try_from(4.0).unwrap());
+        assert_eq!(testimonial.date, date(2024, 5, 12));
+        assert_eq!(testimonial.service, "");
+    }
+
+    #[test]
+    fn reads_nested_json_and_unix_dates() {
+        let json = r#"{"data": {"reviews": [
+            {"author": {"name": "Мария Петрова"}, "rating": {"value": 5},
+             "text": "Отличные брови", "updatedTime": 1715472000000},
+            {"meta": "не отзыв"}
+        ]}}"#;
+        let records = read_json(json, Platform::Yandex).unwrap();
+        assert_eq!(records.len(), 1);
+
+        let testimonial = into_testimonial(records.into_iter().next().unwrap(), "").unwrap();
+        assert_eq!(testimonial.name, "Мария Петрова");
+        assert_eq!(testimonial.rating, Rating::try_from(5.0).unwrap());
+        assert_eq!(testimonial.date, date(2024, 5, 12));
+    }
+
+    #[test]
+    fn resolves_vk_authors_from_profiles() {
+        let json = r#"{"response": {
+            "items": [{"from_id": 7, "text": "Спасибо!", "rating": 5, "date": 1715472000}],
+            "profiles": [{"id": 7, "first_name": "Анна", "last_name": "Смирнова"}]
+        }}"#;
+        let records = read_json(json, Platform::Vk).unwrap();
+        assert_eq!(records[0].name.as_deref(), Some("Анна Смирнова"));
+    }
+
+    #[test]
+    fn reads_schema_org_microdata() {
+        let html = r#"<div itemprop="review">
+            <span itemprop="author"><span itemprop="name">Елена</span></span>
+            <meta itemprop="ratingValue" content="4.5">
+            <meta itemprop="datePublished" content="2024-05-12">
+            <p itemprop="reviewBody"> Аккуратно и быстро </p>
+        </div>"#;
+        let records = read_html(html);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].name.as_deref(), Some("Елена"));
+        assert_eq!(records[0].rating, Some(4.5));
+        assert_eq!(records[0].text.as_deref(), Some("Аккуратно и быстро"));
+        assert_eq!(records[0].date, Some(date(2024, 5, 12)));
+    }
+
+    #[test]
+    fn skips_incomplete_records() {
+        let record = |name: Option<&str>, rating: Option<f32>| ReviewRecord {
+            name: name.map(str::to_string),
+            rating,
+            text: Some("Текст".to_string()),
+            date: Some(date(2024, 5, 12)),
+            service: None,
+        };
+        assert!(into_testimonial(record(None, Some(5.0)), "").is_err());
+        assert!(into_testimonial(record(Some("  "), Some(5.0)), "").is_err());
+        assert!(into_testimonial(record(Some("Ольга"), None), "").is_err());
+        assert!(into_testimonial(record(Some("Ольга"), Some(7.0)), "").is_err());
+    }
+
+    #[test]
+    fn fills_initials_and_default_service() {
+        let record = ReviewRecord {
+            name: Some(" ольга мария иванова ".to_string()),
+            rating: Some(5.0),
+            text: Some("Текст".to_string()),
+            date: Some(date(2024, 5, 12)),
+            service: None,
+        };
+        let testimonial = into_testimonial(record, "Брови").unwrap();
+        assert_eq!(testimonial.avatar, "ОМ");
+        assert_eq!(testimonial.name, "ольга мария иванова");
+        assert_eq!(testimonial.service, "Брови");
+    }
+
+    #[test]
+    fn dedup_ignores_case_punctuation_and_long_tails() {
+        let testimonial = |name: &str, text: &str| Testimonial {
+            name: name.to_string(),
+            avatar: String::new(),
+            rating: Rating::try_from(5.0).unwrap(),
+            text: text.to_string(),
+            date: date(2024, 5, 12),
+            service: String::new(),
+            service_ids: Vec::new(),
+        };
+        let prefix = "а".repeat(DEDUP_PREFIX_CHARS);
+
+        assert_eq!(
+            dedup_key(&testimonial("Ольга", "Всё понравилось!")),
+            dedup_key(&testimonial("ольга ", "всё, понравилось"))
+        );
+        assert_eq!(
+            dedup_key(&testimonial("Ольга", &format!("{}, спасибо", prefix))),
+            dedup_key(&testimonial("Ольга", &format!("{} — приду ещё", prefix)))
+        );
+        assert_ne!(
+            dedup_key(&testimonial("Ольга", "Всё понравилось")),
+            dedup_key(&testimonial("Мария", "Всё понравилось"))
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::cli::args::Args;
+use crate::content_loader;
+use crate::data::Content;
+use crate::qr::Qr;
+use std::fs;
+
+/// Стили прайса: та же раскладка, что у страницы `?print=prices`, но без
+/// Tailwind и шрифтов с CDN, чтобы файл открывался и печатался без сети.
+const STYLE: &str = "
+@page { size: A4; margin: 15mm; }
+* { box-sizing: border-box; }
+body { margin: 0; background: #fffbeb; color: #78350f;
+  font-family: 'Montserrat', 'Helvetica Neue', Arial, sans-serif; }
+article { max-width: 48rem; margin: 2rem auto; background: #fff; padding: 2.5rem;
+  border-radius: 1rem; box-shadow: 0 20px 25px -5px rgb(0 0 0 / 0.1); }
+header, footer { display: flex; justify-content: space-between; gap: 1.5rem; }
+header { align-items: flex-end; border-bottom: 2px solid #f59e0b;
+  padding-bottom: 1rem; margin-bottom: 1.5rem; }
+footer { align-items: center; border-top: 2px solid #f59e0b; padding-top: 1rem;
+  margin-top: 0.5rem; font-size: 0.875rem; }
+h1 { font-size: 2.25rem; margin: 0; font-weight: 800; }
+h2 { font-size: 1.125rem; margin: 0 0 0.5rem; color: #d97706;
+  text-transform: uppercase; letter-spacing: 0.025em; }
+p { margin: 0.25rem 0; }
+.muted { color: #b45309; }
+.right { text-align: right; }
+.title { font-size: 1.25rem; font-weight: 700; }
+.columns { column-count: 2; column-gap: 2rem; }
+section { break-inside: avoid; margin-bottom: 1.5rem; }
+ul { list-style: none; margin: 0; padding: 0; }
+li { display: flex; align-items: baseline; gap: 0.5rem; font-size: 0.875rem; margin: 0.25rem 0; }
+.duration { font-size: 0.75rem; color: #d97706; white-space: nowrap; }
+.leader { flex: 1; border-bottom: 1px dotted #fcd34d; }
+.price { font-weight: 600; white-space: nowrap; }
+svg { width: 7rem; height: 7rem; flex-shrink: 0; }
+@media print {
+  body { background: #fff; }
+  article { margin: 0; padding: 0; box-shadow: none; border-radius: 0; max-width: none; }
+}
+@media (max-width: 640px) { .columns { column-count: 1; } }
+";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["content", "url"], &[])?;
+    let content_path = args.get_or("content", "static/content.yaml");
+    let output_path = args
+        .positional
+        .first()
+        .map_or("prices.html", String::as_str);
+
+    let content = content_loader::load_content_file(content_path)?;
+    let site_url = args
+        .get("url")
+        .map(str::to_string)
+        .or_else(|| content.info.site_url.clone())
+        .ok_or("Site address is unknown: set info.site_url in content.yaml or pass --url")?;
+
+    let html = render(&content, &site_url)?;
+    fs::write(output_path, html).map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    println!(
+        "Price sheet written to {}, open it in a browser and print to PDF",
+        output_path
+    );
+    Ok(())
+}
+
+fn render(content: &Content, site_url: &str) -> Result<String, String> {
+    let info = &content.info;
+    let contacts = &content.contacts;
+    let services = &content.services;
+    let qr = Qr::new(site_url)?;
+
+    let categories = services
+        .categories
+        .iter()
+        .map(|category| {
+            let items = category
+                .items
+                .iter()
+                .map(|item| {
+                    format!(
+                        "<li><span>{}</span><span class=\"duration\">{}</span>\
+                         <span class=\"leader\"></span><span class=\"price\">{}</span></li>",
+                        escape(&item.name),
+                        escape(item.duration.as_str()),
+                        escape(item.price.as_str())
+                    )
+                })
+                .collect::<String>();
+            format!(
+                "<section><h2>{}</h2><ul>{}</ul></section>",
+                escape(&category.name),
+                items
+            )
+        })
+        .collect::<String>();
+
+    Ok(format!(
+        "<!DOCTYPE html>
+<html lang=\"ru\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{title} — {name}</title>
+<style>{style}</style>
+</head>
+<body>
+<article>
+<header>
+<div><h1>{name}</h1><p class=\"muted\">{slogan}</p></div>
+<div class=\"right\"><p class=\"title\">{title}</p><p class=\"muted\">{city}</p></div>
+</header>
+<div class=\"columns\">{categories}</div>
+<footer>
+<div><p>{phone}</p><p>{address}</p><p>{hours}</p><p>{url}</p></div>
+<svg viewBox=\"0 0 {size} {size}\" shape-rendering=\"crispEdges\">\
+<rect width=\"100%\" height=\"100%\" fill=\"white\"/><path d=\"{path}\" fill=\"#78350f\"/></svg>
+</footer>
+</article>
+</body>
+</html>
+",
+        title = escape(&services.title),
+        name = escape(&info.name),
+        slogan = escape(&info.slogan),
+        city = escape(&info.city),
+        style = STYLE,
+        categories = categories,
+        phone = escape(&contacts.phone),
+        address = escape(&contacts.address),
+        hours = escape(&contacts.working_hours),
+        url = escape(site_url),
+        size = qr.size,
+        path = qr.path,
+    ))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
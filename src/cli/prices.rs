@@ -0,0 +1,635 @@
+use crate::cli::args::Args;
+use crate::cli::price_sheet;
+use crate::content_format::ContentFormat;
+use crate::content_loader;
+use crate::data::{ServiceCategory, ServiceItem, ServicesSection};
+use crate::pricing::{Duration, Price};
+use crate::text;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+const CATEGORY_FIELDS: &[&str] = &["category", "категория", "раздел"];
+const NAME_FIELDS: &[&str] = &["name", "service", "название", "услуга"];
+const DESCRIPTION_FIELDS: &[&str] = &["description", "описание"];
+const DURATION_FIELDS: &[&str] = &["duration", "длительность", "время"];
+const PRICE_FIELDS: &[&str] = &["price", "цена", "стоимость"];
+const ID_FIELDS: &[&str] = &["id"];
+
+/// Excel открывает CSV в UTF-8 без искажений только с этой меткой в начале.
+const BOM: &str = "\u{feff}";
+
+/// Иконка для категорий, которых ещё нет в content.yaml.
+const DEFAULT_CATEGORY_ICON: &str = "fa-star";
+
+const SERVICES_HEADER: &str = "\
+# Услуги и цены. Файл можно править вручную или собрать из таблицы:
+#   cargo run -- prices import prices.csv
+# Выгрузить текущий прайс в CSV: cargo run -- prices export prices.csv
+
+";
+
+/// Строка прайса из таблицы. Отсутствующие колонки берутся из текущего прайса.
+#[derive(Debug)]
+struct PriceRow {
+    category: String,
+    name: String,
+    description: Option<String>,
+    duration: Option<String>,
+    price: String,
+    id: Option<String>,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    match args.split_first() {
+        Some((command, rest)) if command == "export" => export(rest),
+        Some((command, rest)) if command == "import" => import(rest),
+        Some((command, rest)) if command == "sheet" => price_sheet::run(rest),
+        _ => Err("Expected `prices export`, `prices import` or `prices sheet`".to_string()),
+    }
+}
+
+fn export(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["content", "delimiter"], &[])?;
+    let content_path = args.get_or("content", "static/content.yaml");
+    let output_path = args.positional.first().map_or("prices.csv", String::as_str);
+    let delimiter = match args.get_or("delimiter", ",") {
+        "," => b',',
+        ";" => b';',
+        "tab" | "\t" => b'\t',
+        other => return Err(format!("Unsupported delimiter: {}", other)),
+    };
+
+    let content = content_loader::load_content_file(content_path)?;
+    let bytes = write_csv(&content.services, delimiter)?;
+
+    fs::write(output_path, bytes).map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    println!(
+        "Exported {} services to {}",
+        content.services.items().count(),
+        output_path
+    );
+    Ok(())
+}
+
+fn write_csv(services: &ServicesSection, delimiter: u8) -> Result<Vec<u8>, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(BOM.as_bytes().to_vec());
+    let write_error = |e: csv::Error| format!("Failed to write CSV: {}", e);
+    writer
+        .write_record(["category", "name", "description", "duration", "price", "id"])
+        .map_err(write_error)?;
+    for category in &services.categories {
+        for item in &category.items {
+            writer
+                .write_record([
+                    &category.name,
+                    &item.name,
+                    &item.description,
+                    item.duration.as_str(),
+                    item.price.as_str(),
+                    &item.id,
+                ])
+                .map_err(write_error)?;
+        }
+    }
+    writer
+        .into_inner()
+        .map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+fn import(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["content", "output"], &["dry-run"])?;
+    let [csv_path] = args.positional.as_slice() else {
+        return Err("Expected exactly one CSV file".to_string());
+    };
+    let content_path = args.get_or("content", "static/content.yaml");
+    // Услуги записываются туда, откуда они подключены в главном файле контента.
+    let output_path = match args.get("output") {
+        Some(path) => path.to_string(),
+        None => content_loader::section_file(content_path, "services")?.ok_or_else(|| {
+            format!(
+                "Services are written inside {}, pass --output to choose a file \
+                 and include it with `services: !include <file>`",
+                content_path
+            )
+        })?,
+    };
+    if ContentFormat::detect(&output_path, None)? != ContentFormat::Yaml {
+        return Err(format!(
+            "Only YAML services files can be written: {}",
+            output_path
+        ));
+    }
+
+    let content = content_loader::load_content_file(content_path)?;
+    let text =
+        fs::read_to_string(csv_path).map_err(|e| format!("Failed to read {}: {}", csv_path, e))?;
+    let rows = read_csv(&text)?;
+    if rows.is_empty() {
+        return Err(format!("No services found in {}", csv_path));
+    }
+
+    let services = build_services(&content.services, rows)?;
+    let report = diff_report(&content.services, &services);
+    if report.is_empty() {
+        println!("Price list is up to date, nothing to change");
+        return Ok(());
+    }
+    print!("{}", report);
+
+    // Работы и отзывы ссылаются на id услуг: удалённая услуга не должна их осиротить.
+    let mut updated = content.clone();
+    updated.services = services.clone();
+    updated.validate()?;
+
+    let existing = if Path::new(&output_path).exists() {
+        Some(
+            fs::read_to_string(&output_path)
+                .map_err(|e| format!("Failed to read {}: {}", output_path, e))?,
+        )
+    } else {
+        None
+    };
+    let output = match (&existing, price_changes(&content.services, &services)) {
+        (Some(text), Some(prices)) => {
+            let patched = patch_prices(text, &prices)?;
+            let parsed = ContentFormat::Yaml
+                .parse(&patched)
+                .and_then(|value| serde_json::from_value(value).map_err(|e| e.to_string()));
+            if parsed.as_ref() != Ok(&services) {
+                return Err(format!(
+                    "Failed to update prices in {} in place, edit them by hand",
+                    output_path
+                ));
+            }
+            patched
+        }
+        (Some(text), None) if has_comments(text) => {
+            return Err(format!(
+                "The CSV changes more than prices, and rewriting {} would lose its comments. \
+                 Make these changes by hand or remove the comments first",
+                output_path
+            ));
+        }
+        _ => {
+            let yaml = serde_yaml::to_string(&services)
+                .map_err(|e| format!("Failed to serialize services: {}", e))?;
+            format!("{}{}", SERVICES_HEADER, yaml)
+        }
+    };
+
+    if args.flag("dry-run") {
+        println!("Dry run, {} is not changed", output_path);
+        return Ok(());
+    }
+
+    fs::write(&output_path, output)
+        .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    println!("Written to {}", output_path);
+    Ok(())
+}
+
+/// Новые цены по id, если таблица меняет только цены: тогда файл услуг
+/// правится на месте, а не пересобирается.
+fn price_changes<'a>(
+    old: &ServicesSection,
+    new: &'a ServicesSection,
+) -> Option<HashMap<&'a str, &'a str>> {
+    let same_layout = old.categories.len() == new.categories.len()
+        && old
+            .categories
+            .iter()
+            .zip(&new.categories)
+            .all(|(old, new)| {
+                old.name == new.name
+                    && old.icon == new.icon
+                    && old.description == new.description
+                    && old.items.len() == new.items.len()
+                    && old.items.iter().zip(&new.items).all(|(old, new)| {
+                        let repriced = ServiceItem {
+                            price: new.price.clone(),
+                            ..old.clone()
+                        };
+                        repriced == *new
+                    })
+            });
+
+    same_layout.then(|| {
+        old.items()
+            .zip(new.items())
+            .filter(|(old, new)| old.price != new.price)
+            .map(|(_, new)| (new.id.as_str(), new.price.as_str()))
+            .collect()
+    })
+}
+
+/// Заменяет значения `price` услуг с данными id прямо в тексте файла:
+/// комментарии, кавычки и порядок полей остаются как были.
+fn patch_prices(text: &str, prices: &HashMap<&str, &str>) -> Result<String, String> {
+    // id текущей услуги и отступ её полей
+    let mut current: Option<(String, usize)> = None;
+    let mut patched = HashSet::new();
+    let mut lines = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let field = trimmed.strip_prefix("- ").map_or(trimmed, str::trim_start);
+        let indent = line.len() - field.len();
+
+        if let Some(value) = field.strip_prefix("id:") {
+            let (id, _) = split_comment(value);
+            current = Some((id.trim_matches(['"', '\'']).to_string(), indent));
+        } else if let Some(value) = field.strip_prefix("price:")
+            && let Some((id, id_indent)) = &current
+            && *id_indent == indent
+            && let Some(price) = prices.get(id.as_str())
+        {
+            let (_, comment) = split_comment(value);
+            let quoted = serde_json::to_string(price)
+                .map_err(|e| format!("Failed to quote price: {}", e))?;
+            lines.push(format!("{}price: {}{}", &line[..indent], quoted, comment));
+            patched.insert(id.clone());
+            current = None;
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+
+    if let Some(missing) = prices.keys().find(|id| !patched.contains(**id)) {
+        return Err(format!("No price line found for service {}", missing));
+    }
+    let mut output = lines.join("\n");
+    if text.ends_with('\n') {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Скалярное значение после `ключ:` и комментарий за ним вместе с отступом перед `#`.
+fn split_comment(value: &str) -> (&str, &str) {
+    let start = value.len() - value.trim_start().len();
+    let end = match value[start..].chars().next() {
+        Some(quote @ ('"' | '\'')) => value[start + 1..]
+            .find(quote)
+            .map_or(value.len(), |i| start + i + 2),
+        _ => value.find(" #").unwrap_or(value.len()),
+    };
+    let comment = &value[end..];
+    let value = value[..end].trim();
+    let value = value.strip_prefix(['"', '\'']).unwrap_or(value);
+    (value.strip_suffix(['"', '\'']).unwrap_or(value), comment)
+}
+
+/// Есть ли в файле комментарии, кроме стандартной шапки.
+fn has_comments(text: &str) -> bool {
+    text.strip_prefix(SERVICES_HEADER)
+        .unwrap_or(text)
+        .lines()
+        .any(|line| line.trim_start().starts_with('#') || line.contains(" #"))
+}
+
+fn read_csv(content: &str) -> Result<Vec<PriceRow>, String> {
+    let content = content.trim_start_matches(BOM);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(detect_delimiter(content))
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let column = |fields: &[&str]| {
+        fields
+            .iter()
+            .find_map(|f| headers.iter().position(|h| h == f))
+    };
+    let required = |fields: &[&str]| {
+        column(fields).ok_or_else(|| format!("CSV has no \"{}\" column", fields[0]))
+    };
+    let category_col = required(CATEGORY_FIELDS)?;
+    let name_col = required(NAME_FIELDS)?;
+    let price_col = required(PRICE_FIELDS)?;
+    let description_col = column(DESCRIPTION_FIELDS);
+    let duration_col = column(DURATION_FIELDS);
+    let id_col = column(ID_FIELDS);
+
+    let mut rows = Vec::new();
+    for (index, row) in reader.records().enumerate() {
+        let row = row.map_err(|e| format!("Failed to read CSV row: {}", e))?;
+        let cell = |col: usize| row.get(col).map(str::trim).unwrap_or_default().to_string();
+
+        let name = cell(name_col);
+        if name.is_empty() {
+            continue;
+        }
+        let category = cell(category_col);
+        if category.is_empty() {
+            // Первая строка — заголовок, отсюда сдвиг на 2.
+            return Err(format!("Row {}: \"{}\" has no category", index + 2, name));
+        }
+
+        rows.push(PriceRow {
+            category,
+            name,
+            description: description_col.map(cell),
+            duration: duration_col.map(cell),
+            price: cell(price_col),
+            id: id_col.map(cell).filter(|id| !id.is_empty()),
+        });
+    }
+    Ok(rows)
+}
+
+/// Таблицы с русской локалью сохраняют CSV через точку с запятой.
+fn detect_delimiter(content: &str) -> u8 {
+    let header = content.lines().next().unwrap_or_default();
+    if header.matches(';').count() > header.matches(',').count() {
+        b';'
+    } else {
+        b','
+    }
+}
+
+/// Собирает прайс из строк таблицы. Заголовки секции, иконки и описания
+/// категорий, подробности услуг берутся из текущего прайса, порядок — из таблицы.
+fn build_services(
+    current: &ServicesSection,
+    rows: Vec<PriceRow>,
+) -> Result<ServicesSection, String> {
+    let existing_by_id = current
+        .items()
+        .map(|item| (item.id.as_str(), item))
+        .collect::<HashMap<_, _>>();
+    let existing_by_name = current
+        .items()
+        .map(|item| (item.name.to_lowercase(), item))
+        .collect::<HashMap<_, _>>();
+
+    // Id из таблицы и id узнанных по названию услуг заняты заранее, чтобы
+    // новая услуга не получила id, который ниже в таблице принадлежит другой.
+    let reserved = rows
+        .iter()
+        .filter_map(|row| match &row.id {
+            Some(id) => Some(id.as_str()),
+            None => existing_by_name
+                .get(&row.name.to_lowercase())
+                .map(|item| item.id.as_str()),
+        })
+        .map(str::to_string)
+        .collect::<HashSet<_>>();
+
+    let mut categories: Vec<ServiceCategory> = Vec::new();
+    let mut ids = HashSet::new();
+
+    for row in rows {
+        let existing = match &row.id {
+            Some(id) => existing_by_id.get(id.as_str()),
+            None => existing_by_name.get(&row.name.to_lowercase()),
+        };
+
+        let id = match (&row.id, existing) {
+            (Some(id), _) => id.clone(),
+            (None, Some(item)) => item.id.clone(),
+            (None, None) => unique_id(&text::slug(&row.name), &ids, &reserved),
+        };
+        if !ids.insert(id.clone()) {
+            return Err(format!("Duplicate service id in CSV: {}", id));
+        }
+
+        let item = ServiceItem {
+            id,
+            description: row
+                .description
+                .or_else(|| existing.map(|item| item.description.clone()))
+                .unwrap_or_default(),
+            duration: row
+                .duration
+                .map(Duration::from)
+                .or_else(|| existing.map(|item| item.duration.clone()))
+                .unwrap_or_else(|| Duration::from(String::new())),
+            name: row.name,
+            price: Price::from(row.price),
+            details: existing
+                .map(|item| item.details.clone())
+                .unwrap_or_default(),
+        };
+
+        match categories.iter_mut().find(|c| c.name == row.category) {
+            Some(category) => category.items.push(item),
+            None => {
+                let known = current.categories.iter().find(|c| c.name == row.category);
+                if known.is_none() {
+                    eprintln!(
+                        "New category \"{}\": set its icon and description in the services file",
+                        row.category
+                    );
+                }
+                categories.push(ServiceCategory {
+                    name: row.category,
+                    icon: known.map_or(DEFAULT_CATEGORY_ICON.to_string(), |c| c.icon.clone()),
+                    description: known.map(|c| c.description.clone()).unwrap_or_default(),
+                    items: vec![item],
+                });
+            }
+        }
+    }
+
+    Ok(ServicesSection {
+        title: current.title.clone(),
+        subtitle: current.subtitle.clone(),
+        categories,
+        combos: current.combos.clone(),
+    })
+}
+
+/// Отчёт об изменениях прайса; пустой, если менять нечего.
+fn diff_report(old: &ServicesSection, new: &ServicesSection) -> String {
+    let old_items = by_id(old);
+    let new_items = by_id(new);
+    let title = |category: &ServiceCategory, item: &ServiceItem| {
+        format!("{} / {}", category.name, item.name)
+    };
+
+    let mut prices = Vec::new();
+    let mut added = Vec::new();
+    let mut other = Vec::new();
+    for category in &new.categories {
+        for item in &category.items {
+            let Some((old_category, old_item)) = old_items.get(item.id.as_str()) else {
+                added.push(format!("  + {}: {}", title(category, item), item.price));
+                continue;
+            };
+
+            if old_item.price != item.price {
+                prices.push(format!(
+                    "  {}: {} → {}",
+                    title(category, item),
+                    old_item.price,
+                    item.price
+                ));
+            }
+            let changed = [
+                ("category", old_category.name != category.name),
+                ("name", old_item.name != item.name),
+                ("description", old_item.description != item.description),
+                ("duration", old_item.duration != item.duration),
+            ]
+            .into_iter()
+            .filter_map(|(field, changed)| changed.then_some(field))
+            .collect::<Vec<_>>();
+            if !changed.is_empty() {
+                other.push(format!(
+                    "  {}: {}",
+                    title(category, item),
+                    changed.join(", ")
+                ));
+            }
+        }
+    }
+
+    let removed = old
+        .categories
+        .iter()
+        .flat_map(|c| c.items.iter().map(move |item| (c, item)))
+        .filter(|(_, item)| !new_items.contains_key(item.id.as_str()))
+        .map(|(category, item)| format!("  - {}: {}", title(category, item), item.price))
+        .collect::<Vec<_>>();
+
+    [
+        ("Price changes", prices),
+        ("New services", added),
+        ("Removed services", removed),
+        ("Other changes", other),
+    ]
+    .into_iter()
+    .filter(|(_, lines)| !lines.is_empty())
+    .map(|(heading, lines)| format!("{}:\n{}\n", heading, lines.join("\n")))
+    .collect()
+}
+
+/// Услуги по id вместе с их категориями.
+fn by_id(services: &ServicesSection) -> HashMap<&str, (&ServiceCategory, &ServiceItem)> {
+    services
+        .categories
+        .iter()
+        .flat_map(|c| {
+            c.items
+                .iter()
+                .map(move |item| (item.id.as_str(), (c, item)))
+        })
+        .collect()
+}
+
+fn unique_id(base: &str, taken: &HashSet<String>, reserved: &HashSet<String>) -> String {
+    let base = if base.is_empty() { "service" } else { base };
+    (1..)
+        .map(|n| match n {
+            1 => base.to_string(),
+            n => format!("{}-{}", base, n),
+        })
+        .find(|id| !taken.contains(id) && !reserved.contains(id))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVICES_PATH: &str = "static/content/services.yaml";
+
+    fn services() -> ServicesSection {
+        content_loader::load_content_file("static/content.yaml")
+            .unwrap()
+            .services
+    }
+
+    fn parse(text: &str) -> ServicesSection {
+        serde_json::from_value(ContentFormat::Yaml.parse(text).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn exported_csv_imports_without_changes() {
+        let current = services();
+        let bytes = write_csv(&current, b';').unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with(BOM));
+        assert!(
+            text.trim_start_matches(BOM)
+                .starts_with("category;name;description;duration;price;id\n")
+        );
+
+        let rows = read_csv(&text).unwrap();
+        assert_eq!(rows.len(), current.items().count());
+        assert!(rows.iter().all(|row| row.id.is_some()));
+
+        let imported = build_services(&current, rows).unwrap();
+        assert_eq!(diff_report(&current, &imported), "");
+        assert_eq!(price_changes(&current, &imported), Some(HashMap::new()));
+    }
+
+    #[test]
+    fn price_changes_are_patched_in_place() {
+        let current = services();
+        let text = fs::read_to_string(SERVICES_PATH).unwrap();
+
+        let mut expected = current.clone();
+        for item in expected.categories.iter_mut().flat_map(|c| &mut c.items) {
+            if item.id == "day-makeup" || item.id == "brow-lamination" {
+                item.price = Price::from("от 5000 ₽".to_string());
+            }
+        }
+        let prices = price_changes(&current, &expected).unwrap();
+        assert_eq!(prices.len(), 2);
+
+        let patched = patch_prices(&text, &prices).unwrap();
+        assert_eq!(parse(&patched), expected);
+        let changed = text
+            .lines()
+            .zip(patched.lines())
+            .filter(|(old, new)| old != new)
+            .map(|(_, new)| new.trim())
+            .collect::<Vec<_>>();
+        assert_eq!(changed, ["price: \"от 5000 ₽\""; 2]);
+        assert_eq!(text.lines().count(), patched.lines().count());
+    }
+
+    #[test]
+    fn patch_keeps_quoting_and_trailing_comments() {
+        let text = "items:\n  - id: 'a' # первая\n    price: 100 # до 1 мая\n    extra:\n      price: 1\n  - id: b\n    price: \"200\"\n";
+        let prices = HashMap::from([("a", "150 ₽")]);
+        assert_eq!(
+            patch_prices(text, &prices).unwrap(),
+            "items:\n  - id: 'a' # первая\n    price: \"150 ₽\" # до 1 мая\n    extra:\n      price: 1\n  - id: b\n    price: \"200\"\n"
+        );
+
+        let prices = HashMap::from([("missing", "1 ₽")]);
+        assert!(patch_prices(text, &prices).is_err());
+    }
+
+    #[test]
+    fn structural_changes_are_not_patched() {
+        let current = services();
+        let mut renamed = current.clone();
+        renamed.categories[0].items[0].name = "Макияж на каждый день".to_string();
+        assert_eq!(price_changes(&current, &renamed), None);
+
+        let mut removed = current.clone();
+        removed.categories[0].items.pop();
+        assert_eq!(price_changes(&current, &removed), None);
+    }
+
+    #[test]
+    fn detects_comments_beyond_the_header() {
+        assert!(!has_comments(&format!(
+            "{}title: \"Услуги\"\n",
+            SERVICES_HEADER
+        )));
+        assert!(has_comments("title: \"Услуги\" # черновик\n"));
+        assert!(has_comments("items:\n  # скидка до мая\n  - id: a\n"));
+    }
+}
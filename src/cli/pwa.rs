@@ -0,0 +1,206 @@
+use crate::cli::args::Args;
+use crate::content_loader;
+use crate::data::Content;
+use crate::text;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Цвета приложения: amber-600 и amber-50 из палитры сайта.
+const THEME_COLOR: &str = "#d97706";
+const BACKGROUND_COLOR: &str = "#fffbeb";
+
+/// Файлы PWA по адресам сайта; `sw.js` и `index.html` ссылаются на эти пути.
+const PWA_DIR: &str = "/static/pwa";
+
+const SERVICE_WORKER: &str = "sw.js";
+/// Строка `sw.js` с именем кэша; имя пересчитывается командой.
+const CACHE_LINE: &str = "const CACHE = ";
+
+/// `--check` ничего не пишет, а падает, если файлы устарели: для проверки в CI.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["content"], &["check"])?;
+    let content_path = args.get_or("content", "static/content.yaml");
+    let (content, files) = content_loader::load_content_files(content_path)?;
+
+    let output = PWA_DIR.trim_start_matches('/');
+    fs::create_dir_all(output).map_err(|e| format!("Failed to create {}: {}", output, e))?;
+
+    let precache = precache_urls(&content, &files);
+    let precache_json = pretty(&json!(precache))?;
+    let worker = fs::read_to_string(SERVICE_WORKER)
+        .map_err(|e| format!("Failed to read {}: {}", SERVICE_WORKER, e))?;
+    let outputs = [
+        (
+            Path::new(output).join("manifest.webmanifest"),
+            pretty(&manifest(&content))?,
+        ),
+        (Path::new(output).join("icon.svg"), icon(&content)),
+        (
+            PathBuf::from(SERVICE_WORKER),
+            service_worker(&worker, &precache_json)?,
+        ),
+        (Path::new(output).join("precache.json"), precache_json),
+    ];
+    if args.flag("check") {
+        let stale = outputs
+            .iter()
+            .filter(|(path, text)| fs::read_to_string(path).ok().as_ref() != Some(text))
+            .map(|(path, _)| path.display().to_string())
+            .collect::<Vec<_>>();
+        if !stale.is_empty() {
+            return Err(format!(
+                "Outdated, run `cargo run -- pwa`: {}",
+                stale.join(", ")
+            ));
+        }
+        println!(
+            "PWA files in {} and {} are up to date",
+            output, SERVICE_WORKER
+        );
+        return Ok(());
+    }
+
+    for (path, text) in outputs {
+        fs::write(&path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    println!(
+        "Written manifest, icon and {} precached files to {}",
+        precache.len(),
+        output
+    );
+    Ok(())
+}
+
+fn manifest(content: &Content) -> serde_json::Value {
+    let info = &content.info;
+    json!({
+        "name": format!("{} · {}", info.name, info.city),
+        "short_name": info.name,
+        "description": info.slogan,
+        "lang": "ru",
+        "start_url": "/",
+        "scope": "/",
+        "display": "standalone",
+        "theme_color": THEME_COLOR,
+        "background_color": BACKGROUND_COLOR,
+        "icons": [{
+            "src": format!("{}/icon.svg", PWA_DIR),
+            "sizes": "any",
+            "type": "image/svg+xml",
+            "purpose": "any maskable",
+        }],
+    })
+}
+
+/// Иконка с инициалами салона: «Я Инна» → «ЯИ».
+fn icon(content: &Content) -> String {
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 512 512">
+<rect width="512" height="512" fill="{}"/>
+<text x="256" y="256" dy="0.35em" text-anchor="middle" font-family="Montserrat, Arial, sans-serif" font-size="200" font-weight="700" fill="{}">{}</text>
+</svg>
+"##,
+        THEME_COLOR,
+        BACKGROUND_COLOR,
+        text::initials(&content.info.name)
+    )
+}
+
+/// Файлы для работы без сети: контент, юридические документы и картинки
+/// первого экрана. Сборку wasm сервис-воркер находит сам по ссылкам в index.html.
+fn precache_urls(content: &Content, files: &[String]) -> Vec<String> {
+    let mut urls = files.to_vec();
+    urls.extend(content.testimonials.imported.clone());
+    urls.extend(content.legal.iter().map(|document| document.file.clone()));
+    urls.extend([
+        content.hero.background_image.clone(),
+        content.hero.background_image_mobile.clone(),
+        content.about.image.clone(),
+        format!("{}/manifest.webmanifest", PWA_DIR),
+        format!("{}/icon.svg", PWA_DIR),
+    ]);
+
+    let mut result: Vec<String> = Vec::new();
+    for url in urls {
+        // Внешние картинки (CDN) кэшируются уже при просмотре
+        if url.contains("://") {
+            continue;
+        }
+        let url = format!("/{}", url.trim_start_matches('/'));
+        if !result.contains(&url) {
+            result.push(url);
+        }
+    }
+    result
+}
+
+/// `sw.js` с именем кэша из хэша списка предзагрузки. Новый список меняет
+/// `sw.js`, браузер ставит новый воркер, и при активации старый кэш удаляется.
+fn service_worker(template: &str, precache: &str) -> Result<String, String> {
+    let version = Sha256::digest(precache.as_bytes())
+        .iter()
+        .take(6)
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    let mut found = false;
+    let mut worker = template
+        .lines()
+        .map(|line| {
+            if line.starts_with(CACHE_LINE) {
+                found = true;
+                format!("{}\"yainna-{}\";", CACHE_LINE, version)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !found {
+        return Err(format!(
+            "{} has no `{}` line for the cache name",
+            SERVICE_WORKER, CACHE_LINE
+        ));
+    }
+    if template.ends_with('\n') {
+        worker.push('\n');
+    }
+    Ok(worker)
+}
+
+fn pretty(value: &serde_json::Value) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map(|json| json + "\n")
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKER: &str = "// Сервис-воркер\nconst CACHE = \"yainna-v1\";\nconst X = 1;\n";
+
+    #[test]
+    fn cache_name_follows_the_precache_list() {
+        let first = service_worker(WORKER, "[\"/static/content.yaml\"]\n").unwrap();
+        let second = service_worker(WORKER, "[\"/static/content.json\"]\n").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            service_worker(&first, "[\"/static/content.yaml\"]\n").unwrap(),
+            first
+        );
+
+        let cache = first.lines().nth(1).unwrap();
+        assert!(cache.starts_with("const CACHE = \"yainna-"), "{}", cache);
+        assert_eq!(cache.len(), "const CACHE = \"yainna-\";".len() + 12);
+        assert!(first.ends_with("const X = 1;\n"));
+    }
+
+    #[test]
+    fn worker_without_cache_line_is_an_error() {
+        assert!(service_worker("self.addEventListener();\n", "[]").is_err());
+    }
+}
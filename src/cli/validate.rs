@@ -0,0 +1,18 @@
+use crate::cli::args::Args;
+use crate::content_loader;
+
+/// Проверка контента перед публикацией. На сайте ошибочные записи только
+/// пропускаются с предупреждением в консоли, здесь любая ошибка — отказ.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args = Args::parse(args, &["content"], &[])?;
+    let content_path = args.get_or("content", "static/content.yaml");
+    let content = content_loader::load_content_file(content_path)?;
+
+    for image in content.missing_alt() {
+        println!("Warning: image without alt: {}", image);
+    }
+    content.validate()?;
+
+    println!("{} is valid", content_path);
+    Ok(())
+}
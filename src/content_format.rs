@@ -0,0 +1,219 @@
+use serde_json::Value;
+
+#[cfg(not(any(feature = "yaml", feature = "json", feature = "toml")))]
+compile_error!("Enable at least one content format feature: yaml, json or toml");
+
+/// Ключ ссылки на другой файл: `{ "$ref": "content/portfolio.json" }`.
+pub const REF_KEY: &str = "$ref";
+
+/// Формат файла контента. Набор форматов задаётся фичами `yaml`, `json`, `toml`,
+/// чтобы в wasm-сборку попадали только нужные сайту парсеры.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContentFormat {
+    #[cfg(feature = "yaml")]
+    Yaml,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "toml")]
+    Toml,
+}
+
+impl ContentFormat {
+    pub fn from_path(path: &str) -> Option<Self> {
+        let path = path.split(['?', '#']).next().unwrap_or(path);
+        let extension = path.rsplit_once('.')?.1.to_lowercase();
+        match extension.as_str() {
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(ContentFormat::Yaml),
+            #[cfg(feature = "json")]
+            "json" => Some(ContentFormat::Json),
+            #[cfg(feature = "toml")]
+            "toml" => Some(ContentFormat::Toml),
+            _ => None,
+        }
+    }
+
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim().to_lowercase();
+        match mime.as_str() {
+            #[cfg(feature = "yaml")]
+            "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml" => {
+                Some(ContentFormat::Yaml)
+            }
+            #[cfg(feature = "json")]
+            "application/json" | "text/json" => Some(ContentFormat::Json),
+            #[cfg(feature = "toml")]
+            "application/toml" | "text/toml" | "text/x-toml" => Some(ContentFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// Формат по расширению файла, а если оно неизвестно — по `Content-Type`.
+    pub fn detect(path: &str, content_type: Option<&str>) -> Result<Self, String> {
+        Self::from_path(path)
+            .or_else(|| content_type.and_then(Self::from_content_type))
+            .ok_or_else(|| {
+                format!(
+                    "Cannot determine content format of {} (Content-Type: {}), \
+                     or the format is disabled in this build",
+                    path,
+                    content_type.unwrap_or("unknown")
+                )
+            })
+    }
+
+    /// Разбирает текст в общее дерево значений. YAML-теги `!include путь`
+    /// превращаются в `{ "$ref": путь }`, как в JSON и TOML.
+    pub fn parse(self, text: &str) -> Result<Value, String> {
+        match self {
+            #[cfg(feature = "yaml")]
+            ContentFormat::Yaml => {
+                let value = serde_yaml::from_str::<serde_yaml::Value>(text)
+                    .map_err(|e| format!("Failed to parse YAML: {}", e))?;
+                yaml_to_json(value)
+            }
+            #[cfg(feature = "json")]
+            ContentFormat::Json => {
+                serde_json::from_str(text).map_err(|e| format!("Failed to parse JSON: {}", e))
+            }
+            #[cfg(feature = "toml")]
+            ContentFormat::Toml => {
+                let value = toml::from_str::<toml::Value>(text)
+                    .map_err(|e| format!("Failed to parse TOML: {}", e))?;
+                Ok(toml_to_json(value))
+            }
+        }
+    }
+}
+
+/// Даты TOML (`date = 2024-05-01`) становятся строками, как в YAML и JSON.
+#[cfg(feature = "toml")]
+fn toml_to_json(value: toml::Value) -> Value {
+    use toml::Value as Toml;
+
+    match value {
+        Toml::String(s) => Value::String(s),
+        Toml::Integer(i) => Value::from(i),
+        Toml::Float(f) => serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number),
+        Toml::Boolean(b) => Value::Bool(b),
+        Toml::Datetime(datetime) => Value::String(datetime.to_string()),
+        Toml::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        Toml::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_to_json(value: serde_yaml::Value) -> Result<Value, String> {
+    use serde_yaml::Value as Yaml;
+
+    Ok(match value {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(b),
+        Yaml::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::from(u)
+            } else {
+                n.as_f64()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .ok_or_else(|| format!("Unsupported number: {}", n))?
+            }
+        }
+        Yaml::String(s) => Value::String(s),
+        Yaml::Sequence(items) => Value::Array(
+            items
+                .into_iter()
+                .map(yaml_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Yaml::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        Yaml::String(s) => s,
+                        Yaml::Number(n) => n.to_string(),
+                        Yaml::Bool(b) => b.to_string(),
+                        other => return Err(format!("Unsupported mapping key: {:?}", other)),
+                    };
+                    Ok((key, yaml_to_json(value)?))
+                })
+                .collect::<Result<_, String>>()?,
+        ),
+        Yaml::Tagged(tagged) if tagged.tag == "include" => {
+            let path = tagged
+                .value
+                .as_str()
+                .ok_or("!include expects a file path")?;
+            serde_json::json!({ REF_KEY: path })
+        }
+        // Так serde_yaml записывает варианты перечислений (`discount: !percent 10`):
+        // в JSON они становятся `{ "percent": 10 }`, как их и читает serde
+        Yaml::Tagged(tagged) => {
+            let tag = tagged.tag.to_string();
+            let key = tag.trim_start_matches('!');
+            serde_json::json!({ key: yaml_to_json(tagged.value)? })
+        }
+    })
+}
+
+#[cfg(all(test, any(feature = "yaml", feature = "toml")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn tagged_yaml_enums_round_trip() {
+        use crate::data::{Combo, Discount};
+
+        let combos = vec![
+            Combo {
+                name: "Брови + ресницы".to_string(),
+                service_ids: vec!["brow-lamination".to_string(), "lash-tinting".to_string()],
+                discount: Discount::Percent(10),
+            },
+            Combo {
+                name: "Образ под ключ".to_string(),
+                service_ids: vec!["evening-makeup".to_string()],
+                discount: Discount::Amount(1000),
+            },
+        ];
+        let yaml = serde_yaml::to_string(&combos).unwrap();
+        assert!(yaml.contains("discount: !percent 10"), "{}", yaml);
+
+        let value = ContentFormat::Yaml.parse(&yaml).unwrap();
+        assert_eq!(value[0]["discount"], serde_json::json!({ "percent": 10 }));
+        assert_eq!(serde_json::from_value::<Vec<Combo>>(value).unwrap(), combos);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn include_tag_becomes_ref() {
+        let value = ContentFormat::Yaml
+            .parse("faq: !include content/faq.yaml\n")
+            .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "faq": { REF_KEY: "content/faq.yaml" } })
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_dates_become_strings() {
+        let value = ContentFormat::Toml
+            .parse("[[items]]\ndate = 2024-05-01\nrating = 5\n")
+            .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "items": [{ "date": "2024-05-01", "rating": 5 }] })
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use crate::content_format::{ContentFormat, REF_KEY};
+use crate::data::{
+    Content, FaqSection, GiftCertificatesSection, ImportedTestimonials, PortfolioSection,
+    TestimonialsSection,
+};
+use futures::future::join_all;
+use gloo_net::http::{Request, Response};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// Главный файл контента; формат определяется по расширению. Путь можно
+/// переопределить при сборке: `YAINNA_CONTENT_PATH=/static/content.json trunk build`.
+pub const CONTENT_PATH: &str = match option_env!("YAINNA_CONTENT_PATH") {
+    Some(path) => path,
+    None => "/static/content.yaml",
+};
+
+/// Ограничение вложенности включений, защищает от циклических ссылок.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Корень файла контента без раскрытых включений; пути включений уже абсолютные.
+pub async fn fetch_root(path: &str) -> Result<Value, String> {
+    fetch_file(path).await
+}
+
+/// Раскрывает включения только в секции `key` и подставляет результат в корень,
+/// чтобы секцию можно было показать, не дожидаясь остальных файлов.
+pub async fn load_section<T: DeserializeOwned>(root: &mut Value, key: &str) -> Result<T, String> {
+    let section = root
+        .get(key)
+        .cloned()
+        .ok_or_else(|| format!("Missing section: {}", key))?;
+    let section = resolve_includes(section).await?;
+
+    if let Some(object) = root.as_object_mut() {
+        object.insert(key.to_string(), section.clone());
+    }
+
+    serde_json::from_value(section).map_err(|e| format!("Failed to parse {}: {}", key, e))
+}
+
+/// Раскрывает все оставшиеся включения и собирает `Content`.
+pub async fn load_content(root: Value) -> Result<Content, String> {
+    let root = resolve_includes(root).await?;
+    let mut content: Content =
+        serde_json::from_value(root).map_err(|e| format!("Failed to parse content: {}", e))?;
+
+    merge_imported(&mut content.testimonials).await;
+    repair(&mut content);
+    for image in content.missing_alt() {
+        gloo_console::warn!("Image without alt:", image);
+    }
+    Ok(content)
+}
+
+/// Секции ниже первого экрана: в браузере их файлы загружаются, только когда
+/// посетитель долистает до места секции.
+const LAZY_SECTIONS: [&str; 4] = ["portfolio", "testimonials", "gift_certificates", "faq"];
+
+/// Забирает из корня отложенные секции, подключённые отдельными файлами;
+/// секции, записанные прямо в корне, и секции из `eager` остаются на месте.
+pub fn take_lazy_sections(root: &mut Value, eager: &[&str]) -> HashMap<String, Value> {
+    let Some(object) = root.as_object_mut() else {
+        return HashMap::new();
+    };
+    let mut sections = HashMap::new();
+    for key in LAZY_SECTIONS.iter().filter(|key| !eager.contains(key)) {
+        if object.get(*key).is_some_and(|v| include_path(v).is_some())
+            && let Some(value) = object.remove(*key)
+        {
+            sections.insert(key.to_string(), value);
+        }
+    }
+    sections
+}
+
+/// Отложенная секция после загрузки её файлов.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LazySection {
+    Portfolio(PortfolioSection),
+    Testimonials(TestimonialsSection),
+    GiftCertificates(GiftCertificatesSection),
+    Faq(FaqSection),
+}
+
+impl LazySection {
+    /// Подставляет секцию в контент и убирает из неё ссылки на неизвестные услуги.
+    pub fn apply(self, content: &mut Content) {
+        match self {
+            Self::Portfolio(section) => content.portfolio = section,
+            Self::Testimonials(section) => content.testimonials = section,
+            Self::GiftCertificates(section) => content.gift_certificates = Some(section),
+            Self::Faq(section) => content.faq = Some(section),
+        }
+        repair(content);
+    }
+}
+
+/// Загружает отложенную секцию, которую вернул `take_lazy_sections`.
+pub async fn load_lazy_section(key: &str, value: Value) -> Result<LazySection, String> {
+    let value = resolve_includes(value).await?;
+    let parse_error = |e: serde_json::Error| format!("Failed to parse {}: {}", key, e);
+    match key {
+        "portfolio" => serde_json::from_value(value)
+            .map(LazySection::Portfolio)
+            .map_err(parse_error),
+        "testimonials" => {
+            let mut section = serde_json::from_value(value).map_err(parse_error)?;
+            merge_imported(&mut section).await;
+            Ok(LazySection::Testimonials(section))
+        }
+        "gift_certificates" => serde_json::from_value(value)
+            .map(LazySection::GiftCertificates)
+            .map_err(parse_error),
+        "faq" => serde_json::from_value(value)
+            .map(LazySection::Faq)
+            .map_err(parse_error),
+        _ => Err(format!("Unknown lazy section: {}", key)),
+    }
+}
+
+async fn merge_imported(testimonials: &mut TestimonialsSection) {
+    if let Some(path) = testimonials.imported.clone() {
+        match fetch_data::<ImportedTestimonials>(&path).await {
+            Ok(imported) => testimonials.items.extend(imported.items),
+            Err(e) => gloo_console::error!("Failed to load imported testimonials:", &e),
+        }
+    }
+}
+
+thread_local! {
+    /// Уже выведенные ошибки контента: после каждой отложенной секции контент
+    /// проверяется заново, и ошибки, которые ничего не убирают, повторялись бы.
+    static REPORTED_ISSUES: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+fn repair(content: &mut Content) {
+    // Ошибка в контенте не должна оставлять посетителя на «Загрузка...»
+    for issue in content.repair() {
+        if REPORTED_ISSUES.with(|reported| reported.borrow_mut().insert(issue.clone())) {
+            gloo_console::warn!("Content issue:", issue);
+        }
+    }
+}
+
+/// Отдельный файл данных (импортированные отзывы, выданные сертификаты)
+/// в любом из поддерживаемых форматов.
+pub async fn fetch_data<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let value = fetch_file(path).await?;
+    serde_json::from_value(value).map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+/// Загружает файлы включений параллельно, уровень за уровнем.
+async fn resolve_includes(mut value: Value) -> Result<Value, String> {
+    for _ in 0..MAX_INCLUDE_DEPTH {
+        let paths = collect_includes(&value);
+        if paths.is_empty() {
+            return Ok(value);
+        }
+
+        let files =
+            join_all(paths.iter().map(|path| async move {
+                fetch_file(path).await.map(|value| (path.clone(), value))
+            }))
+            .await
+            .into_iter()
+            .collect::<Result<HashMap<_, _>, String>>()?;
+
+        value = substitute(value, &files);
+    }
+
+    Err(format!(
+        "Includes are nested deeper than {} levels, check for cycles",
+        MAX_INCLUDE_DEPTH
+    ))
+}
+
+/// Текстовый файл как есть (юридические документы в Markdown).
+pub async fn fetch_text(path: &str) -> Result<String, String> {
+    fetch_response(path)
+        .await?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {}: {:?}", path, e))
+}
+
+async fn fetch_response(path: &str) -> Result<Response, String> {
+    let response = Request::get(path)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {:?}", path, e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch {}: HTTP {}",
+            path,
+            response.status()
+        ));
+    }
+    Ok(response)
+}
+
+async fn fetch_file(path: &str) -> Result<Value, String> {
+    let response = fetch_response(path).await?;
+    let content_type = response.headers().get("content-type");
+    let format = ContentFormat::detect(path, content_type.as_deref())?;
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {}: {:?}", path, e))?;
+
+    parse_file(&text, path, format)
+}
+
+/// Разбирает файл и переводит пути его включений в пути от корня сайта.
+fn parse_file(text: &str, path: &str, format: ContentFormat) -> Result<Value, String> {
+    let value = format
+        .parse(text)
+        .map_err(|e| format!("{} in {}", e, path))?;
+    Ok(rebase(value, directory(path)))
+}
+
+/// Путь включения, если узел — `{ $ref: путь }` (в YAML также `!include путь`).
+fn include_path(value: &Value) -> Option<&str> {
+    match value {
+        Value::Object(object) if object.len() == 1 => object.get(REF_KEY)?.as_str(),
+        _ => None,
+    }
+}
+
+fn collect_includes(value: &Value) -> Vec<String> {
+    fn walk(value: &Value, seen: &mut HashSet<String>, out: &mut Vec<String>) {
+        if let Some(path) = include_path(value) {
+            if seen.insert(path.to_string()) {
+                out.push(path.to_string());
+            }
+            return;
+        }
+        match value {
+            Value::Array(items) => items.iter().for_each(|v| walk(v, seen, out)),
+            Value::Object(object) => object.values().for_each(|v| walk(v, seen, out)),
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(value, &mut HashSet::new(), &mut out);
+    out
+}
+
+fn substitute(value: Value, files: &HashMap<String, Value>) -> Value {
+    if let Some(file) = include_path(&value).and_then(|path| files.get(path)) {
+        return file.clone();
+    }
+    map_children(value, &|child| substitute(child, files))
+}
+
+fn rebase(value: Value, base: &str) -> Value {
+    if let Some(path) = include_path(&value) {
+        let mut object = Map::new();
+        object.insert(REF_KEY.to_string(), Value::from(join_path(base, path)));
+        return Value::Object(object);
+    }
+    map_children(value, &|child| rebase(child, base))
+}
+
+fn map_children(value: Value, f: &dyn Fn(Value) -> Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(f).collect()),
+        Value::Object(object) => {
+            Value::Object(object.into_iter().map(|(k, v)| (k, f(v))).collect())
+        }
+        other => other,
+    }
+}
+
+fn directory(path: &str) -> &str {
+    path.rfind('/').map_or("", |i| &path[..=i])
+}
+
+fn join_path(base: &str, path: &str) -> String {
+    if path.starts_with('/') || path.contains("://") {
+        path.to_string()
+    } else {
+        format!("{}{}", base, path)
+    }
+}
+
+/// Синхронная загрузка для консольных команд: файлы читаются с диска,
+/// пути от корня сайта (`/static/...`) считаются от текущего каталога.
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+pub fn load_content_file(path: &str) -> Result<Content, String> {
+    load_content_files(path).map(|(content, _)| content)
+}
+
+/// Файл, из которого подключена секция `key` главного файла `path`;
+/// `None`, если секция записана прямо в главном файле.
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+pub fn section_file(path: &str, key: &str) -> Result<Option<String>, String> {
+    let root = read_local(path)?;
+    Ok(root
+        .get(key)
+        .and_then(include_path)
+        .map(|file| file.trim_start_matches('/').to_string()))
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+fn read_local(path: &str) -> Result<Value, String> {
+    let local = path.trim_start_matches('/');
+    let text =
+        std::fs::read_to_string(local).map_err(|e| format!("Failed to read {}: {}", local, e))?;
+    parse_file(&text, path, ContentFormat::detect(path, None)?)
+}
+
+/// Контент и пути всех прочитанных файлов, начиная с главного.
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+pub fn load_content_files(path: &str) -> Result<(Content, Vec<String>), String> {
+    let (value, files_read) = resolve_local_includes(path, &read_local)?;
+    let content =
+        serde_json::from_value(value).map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+    Ok((content, files_read))
+}
+
+/// Раскрывает включения файла `path`, читая файлы через `read`.
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+fn resolve_local_includes(
+    path: &str,
+    read: &dyn Fn(&str) -> Result<Value, String>,
+) -> Result<(Value, Vec<String>), String> {
+    let mut value = read(path)?;
+    let mut files_read = vec![path.to_string()];
+    for _ in 0..MAX_INCLUDE_DEPTH {
+        let paths = collect_includes(&value);
+        if paths.is_empty() {
+            return Ok((value, files_read));
+        }
+
+        let files = paths
+            .iter()
+            .map(|p| read(p).map(|v| (p.clone(), v)))
+            .collect::<Result<HashMap<_, _>, String>>()?;
+        value = substitute(value, &files);
+        files_read.extend(paths);
+    }
+
+    Err(format!(
+        "Includes are nested deeper than {} levels, check for cycles",
+        MAX_INCLUDE_DEPTH
+    ))
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "yaml"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Резолвит включения по набору YAML-файлов в памяти.
+    fn resolve(files: &[(&str, &str)], path: &str) -> Result<(Value, Vec<String>), String> {
+        let files = files.iter().copied().collect::<HashMap<_, _>>();
+        resolve_local_includes(path, &|p| {
+            let text = files
+                .get(p)
+                .ok_or_else(|| format!("Failed to read {}", p))?;
+            parse_file(text, p, ContentFormat::Yaml)
+        })
+    }
+
+    #[test]
+    fn resolves_nested_includes_relative_to_each_file() {
+        let files = [
+            ("/static/content.yaml", "faq: !include content/faq.yaml\n"),
+            (
+                "/static/content/faq.yaml",
+                "title: Вопросы\nitems:\n  $ref: faq/items.yaml\n",
+            ),
+            ("/static/content/faq/items.yaml", "- q: Где?\n  a: В Сочи\n"),
+        ];
+        let (value, files_read) = resolve(&files, "/static/content.yaml").unwrap();
+
+        assert_eq!(
+            value,
+            json!({"faq": {"title": "Вопросы", "items": [{"q": "Где?", "a": "В Сочи"}]}})
+        );
+        assert_eq!(
+            files_read,
+            [
+                "/static/content.yaml",
+                "/static/content/faq.yaml",
+                "/static/content/faq/items.yaml"
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_a_file_included_twice_once() {
+        let files = [
+            (
+                "/a.yaml",
+                "first: !include b.yaml\nsecond: !include /b.yaml\n",
+            ),
+            ("/b.yaml", "ok: true\n"),
+        ];
+        let (value, files_read) = resolve(&files, "/a.yaml").unwrap();
+        assert_eq!(
+            value,
+            json!({"first": {"ok": true}, "second": {"ok": true}})
+        );
+        assert_eq!(files_read, ["/a.yaml", "/b.yaml"]);
+    }
+
+    #[test]
+    fn cyclic_includes_fail_instead_of_recursing() {
+        let files = [
+            ("/a.yaml", "next: !include b.yaml\n"),
+            ("/b.yaml", "next: !include a.yaml\n"),
+        ];
+        let error = resolve(&files, "/a.yaml").unwrap_err();
+        assert!(error.contains("check for cycles"), "{}", error);
+
+        let files = [("/self.yaml", "again: !include self.yaml\n")];
+        assert!(resolve(&files, "/self.yaml").is_err());
+    }
+
+    #[test]
+    fn missing_include_is_an_error() {
+        let files = [("/a.yaml", "faq: !include missing.yaml\n")];
+        let error = resolve(&files, "/a.yaml").unwrap_err();
+        assert!(error.contains("/missing.yaml"), "{}", error);
+    }
+}
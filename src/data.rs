@@ -1,18 +1,230 @@
-use serde::Deserialize;
+use crate::date::CalendarDate;
+use crate::markdown;
+use crate::pricing::{Duration, Price};
+use crate::rating::{Rating, RatingSummary};
+use crate::text;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Префикс якорей вопросов: ссылка `#faq-...` открывает вопрос.
+pub const FAQ_ANCHOR_PREFIX: &str = "faq-";
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct Content {
     pub hero: HeroSection,
     pub about: AboutSection,
     pub services: ServicesSection,
+    /// Пусто, пока секция не загружена (в браузере она догружается при прокрутке).
+    #[serde(default)]
     pub portfolio: PortfolioSection,
+    #[serde(default)]
     pub testimonials: TestimonialsSection,
+    /// Раздел «Вопросы и ответы»; без него раздел не показывается.
+    #[serde(default)]
+    pub faq: Option<FaqSection>,
+    /// Подарочные сертификаты; без секции раздел не показывается.
+    #[serde(default)]
+    pub gift_certificates: Option<GiftCertificatesSection>,
+    /// Коды приглашений и промокоды для ссылок `?ref=` и `?promo=`.
+    #[serde(default)]
+    pub promo_codes: Vec<PromoCode>,
+    /// Счётчик посещаемости; без него скрипты аналитики не подключаются.
+    #[serde(default)]
+    pub analytics: Option<AnalyticsSection>,
+    /// Юридические документы: политика конфиденциальности, согласие на обработку данных.
+    #[serde(default)]
+    pub legal: Vec<LegalDocument>,
     pub contact: ContactSection,
     pub footer: FooterSection,
     pub info: SalonInfo,
     pub contacts: Contacts,
 }
 
+impl Content {
+    /// Строгая проверка для консольных команд: любая ошибка контента — отказ.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+    pub fn validate(&self) -> Result<(), String> {
+        let issues = self.clone().repair();
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues.join("\n"))
+        }
+    }
+
+    /// Проверяет, что id услуг уникальны, а работы, отзывы, комплексы и сертификаты
+    /// ссылаются только на существующие услуги; что якоря вопросов FAQ, промокоды
+    /// и id юридических документов не повторяются. Ошибочные ссылки и записи
+    /// убираются, чтобы сайт показался и с одной опечаткой в контенте;
+    /// возвращается список найденных ошибок.
+    pub fn repair(&mut self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        let mut ids = HashSet::new();
+        for item in self.services.items() {
+            if item.id.trim().is_empty() {
+                issues.push(format!("Service \"{}\" has an empty id", item.name));
+            } else if !ids.insert(item.id.clone()) {
+                issues.push(format!("Duplicate service id: {}", item.id));
+            }
+        }
+
+        for work in &mut self.portfolio.items {
+            let owner = format!("Portfolio item \"{}\"", work.title);
+            retain_known(&ids, &owner, &mut work.service_ids, &mut issues);
+        }
+
+        // Скидка на неполный комплекс была бы неверной, поэтому комплекс убирается целиком
+        self.services.combos.retain(|combo| {
+            let unknown = combo.service_ids.iter().find(|id| !ids.contains(*id));
+            if let Some(id) = unknown {
+                issues.push(format!(
+                    "Combo \"{}\" references unknown service id: {}",
+                    combo.name, id
+                ));
+            }
+            unknown.is_none()
+        });
+
+        if self
+            .contact
+            .directions
+            .as_ref()
+            .is_some_and(|directions| directions.steps.is_empty())
+        {
+            issues.push("Directions need at least one step".to_string());
+            self.contact.directions = None;
+        }
+
+        for testimonial in &mut self.testimonials.items {
+            let owner = format!("Testimonial from \"{}\"", testimonial.name);
+            retain_known(&ids, &owner, &mut testimonial.service_ids, &mut issues);
+        }
+
+        if let Some(faq) = &self.faq {
+            let mut anchors = HashSet::new();
+            for item in faq.items() {
+                if !anchors.insert(item.anchor()) {
+                    issues.push(format!(
+                        "Duplicate FAQ anchor {} for \"{}\", set a unique id",
+                        item.anchor(),
+                        item.question
+                    ));
+                }
+            }
+        }
+
+        if let Some(certificates) = &mut self.gift_certificates {
+            retain_known(
+                &ids,
+                "Gift certificates",
+                &mut certificates.service_ids,
+                &mut issues,
+            );
+            if certificates.amounts.is_empty() && certificates.service_ids.is_empty() {
+                issues.push("Gift certificates need amounts or service_ids".to_string());
+                self.gift_certificates = None;
+            }
+        }
+
+        // Без оператора документы назвали бы оператором бренд салона
+        if !self.legal.is_empty() && self.info.legal_name().is_none() {
+            issues.push(
+                "Legal documents need info.legal_name, the personal data operator; \
+                 they are hidden until it is set"
+                    .to_string(),
+            );
+            self.legal.clear();
+        }
+
+        let mut documents = HashSet::new();
+        self.legal.retain(|document| {
+            let unique = documents.insert(document.id.clone());
+            if !unique {
+                issues.push(format!("Duplicate legal document id: {}", document.id));
+            }
+            unique
+        });
+
+        let mut codes = HashSet::new();
+        self.promo_codes.retain(|promo| {
+            let code = crate::promo::normalize(&promo.code);
+            if code.is_empty() {
+                issues.push(format!("Promo code for \"{}\" is empty", promo.banner));
+                false
+            } else if !codes.insert(code) {
+                issues.push(format!("Duplicate promo code: {}", promo.code));
+                false
+            } else {
+                true
+            }
+        });
+
+        issues
+    }
+
+    /// Фото со смыслом, у которых не заполнено описание `alt`. Декоративный фон
+    /// первого экрана и карта с адресом в подписи сюда не попадают.
+    pub fn missing_alt(&self) -> Vec<String> {
+        let mut missing = Vec::new();
+        if !has_alt(&self.about.image_alt) {
+            missing.push(format!("about: {}", self.about.image));
+        }
+        for item in self.services.items() {
+            if !item.details.gallery.is_empty() && !has_alt(&item.details.gallery_alt) {
+                missing.push(format!("services: {} gallery", item.id));
+            }
+        }
+        for work in &self.portfolio.items {
+            if !work.slides().is_empty() && !has_alt(&work.alt) {
+                missing.push(format!("portfolio: {}", work.title));
+            }
+        }
+        let location = &self.contact.location;
+        if location.entrance_photo.is_some() && !has_alt(&location.entrance_photo_alt) {
+            missing.push("contact: entrance_photo".to_string());
+        }
+        if let Some(directions) = &self.contact.directions {
+            for step in &directions.steps {
+                if let Some(photo) = step.photo.as_ref().filter(|_| !has_alt(&step.alt)) {
+                    missing.push(format!("directions: {}", photo));
+                }
+            }
+        }
+        missing
+    }
+
+    /// Число работ и отзывов по каждой услуге, ключ — id услуги. Считаются
+    /// только отзывы, которые секция показывает на дату `today`.
+    pub fn service_stats(&self, today: CalendarDate) -> HashMap<String, ServiceStats> {
+        self.services
+            .items()
+            .map(|item| {
+                let works = self
+                    .portfolio
+                    .items
+                    .iter()
+                    .filter(|work| work.service_ids.contains(&item.id))
+                    .count();
+                let rating = RatingSummary::from_ratings(
+                    self.testimonials
+                        .visible_items(today)
+                        .filter(|t| t.service_ids.contains(&item.id))
+                        .map(|t| t.rating),
+                );
+
+                (item.id.clone(), ServiceStats { works, rating })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceStats {
+    pub works: usize,
+    pub rating: Option<RatingSummary>,
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct HeroSection {
     pub title: String,
@@ -21,10 +233,33 @@ pub struct HeroSection {
     pub background_image: String,
     #[serde(default = "default_mobile_background")]
     pub background_image_mobile: String,
+    /// Описание фона; без него фон считается декоративным.
+    #[serde(default)]
+    pub background_alt: Option<String>,
     pub buttons: Vec<Button>,
     pub icon: String,
 }
 
+/// Убирает из `service_ids` ссылки на несуществующие услуги.
+fn retain_known(
+    ids: &HashSet<String>,
+    owner: &str,
+    service_ids: &mut Vec<String>,
+    issues: &mut Vec<String>,
+) {
+    service_ids.retain(|id| {
+        let known = ids.contains(id);
+        if !known {
+            issues.push(format!("{} references unknown service id: {}", owner, id));
+        }
+        known
+    });
+}
+
+fn has_alt(alt: &Option<String>) -> bool {
+    alt.as_deref().is_some_and(|alt| !alt.trim().is_empty())
+}
+
 fn default_mobile_background() -> String {
     "/static/images/background-image-mobile.jpg".to_string()
 }
@@ -42,6 +277,8 @@ pub struct AboutSection {
     pub title: String,
     pub subtitle: String,
     pub image: String,
+    #[serde(default)]
+    pub image_alt: Option<String>,
     pub description: String,
     pub achievements: Vec<Achievement>,
 }
@@ -53,14 +290,27 @@ pub struct Achievement {
     pub icon: String,
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ServicesSection {
     pub title: String,
     pub subtitle: String,
     pub categories: Vec<ServiceCategory>,
+    /// Скидки на комплексы услуг для калькулятора.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub combos: Vec<Combo>,
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+impl ServicesSection {
+    pub fn items(&self) -> impl Iterator<Item = &ServiceItem> {
+        self.categories.iter().flat_map(|c| c.items.iter())
+    }
+
+    pub fn find(&self, id: &str) -> Option<&ServiceItem> {
+        self.items().find(|item| item.id == id)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ServiceCategory {
     pub name: String,
     pub icon: String,
@@ -68,46 +318,461 @@ pub struct ServiceCategory {
     pub items: Vec<ServiceItem>,
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ServiceItem {
+    pub id: String,
     pub name: String,
     pub description: String,
-    pub duration: String,
-    pub price: String,
+    pub duration: Duration,
+    pub price: Price,
+    #[serde(flatten)]
+    pub details: ServiceDetails,
+}
+
+/// Подробности об услуге для раскрывающейся карточки; все поля необязательны.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ServiceDetails {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gallery: Vec<String>,
+    /// Описание фото галереи для экранных чтецов, общее для всех кадров.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gallery_alt: Option<String>,
+    /// «Что входит».
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contraindications: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aftercare: Vec<String>,
+    /// Сколько держится результат: «до 6 недель».
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_lasts: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub faq: Vec<FaqItem>,
+}
+
+impl ServiceDetails {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FaqItem {
+    /// Якорь для ссылки на вопрос (`#faq-<id>`); по умолчанию строится из текста вопроса.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub question: String,
+    pub answer: String,
+}
+
+impl FaqItem {
+    /// id элемента на странице, на него ведут ссылки `#faq-...`.
+    pub fn anchor(&self) -> String {
+        let id = self
+            .id
+            .clone()
+            .unwrap_or_else(|| text::slug(&self.question));
+        format!("{}{}", FAQ_ANCHOR_PREFIX, id)
+    }
+
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.question.to_lowercase().contains(&query)
+            || self.answer.to_lowercase().contains(&query)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct FaqSection {
+    pub title: String,
+    pub subtitle: String,
+    pub groups: Vec<FaqGroup>,
+}
+
+impl FaqSection {
+    pub fn items(&self) -> impl Iterator<Item = &FaqItem> {
+        self.groups.iter().flat_map(|group| group.items.iter())
+    }
+
+    /// Разметка schema.org FAQPage для поисковиков.
+    pub fn json_ld(&self) -> serde_json::Value {
+        serde_json::json!({
+            "@context": "https://schema.org",
+            "@type": "FAQPage",
+            "mainEntity": self.items().map(|item| serde_json::json!({
+                "@type": "Question",
+                "name": item.question,
+                "acceptedAnswer": {
+                    "@type": "Answer",
+                    "text": markdown::plain_text(&item.answer),
+                },
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct FaqGroup {
+    pub name: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+    pub items: Vec<FaqItem>,
+}
+
+/// Комплекс: скидка действует, когда выбраны все его услуги.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Combo {
+    pub name: String,
+    pub service_ids: Vec<String>,
+    pub discount: Discount,
+}
+
+/// `discount: { percent: 10 }` или `discount: { amount: 500 }` (рублей).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Discount {
+    Percent(u32),
+    Amount(u32),
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
 pub struct PortfolioSection {
     pub title: String,
     pub subtitle: String,
     pub description: String,
     pub filters: Vec<String>,
+    #[serde(default)]
+    pub filter_mode: FilterMode,
     pub items: Vec<PortfolioItem>,
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterMode {
+    #[default]
+    Any,
+    All,
+}
+
+impl FilterMode {
+    pub fn as_param(self) -> &'static str {
+        match self {
+            FilterMode::Any => "any",
+            FilterMode::All => "all",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct PortfolioItem {
-    pub image: String,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub media: Vec<Media>,
+    /// Что на фото — для экранных чтецов; по умолчанию название работы.
+    #[serde(default)]
+    pub alt: Option<String>,
     pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub service_ids: Vec<String>,
     pub title: String,
     pub description: String,
 }
 
+impl PortfolioItem {
+    /// Теги работы; без явного списка тегом служит категория.
+    pub fn tags(&self) -> Vec<String> {
+        if self.tags.is_empty() {
+            vec![self.category.clone()]
+        } else {
+            self.tags.clone()
+        }
+    }
+
+    /// Кадры карточки: `media`, а если список пуст — одиночное `image`.
+    /// Карусели разворачиваются в отдельные фото.
+    pub fn slides(&self) -> Vec<Media> {
+        let media = if self.media.is_empty() {
+            self.image
+                .iter()
+                .map(|src| Media::Image { src: src.clone() })
+                .collect()
+        } else {
+            self.media.clone()
+        };
+
+        media
+            .into_iter()
+            .flat_map(|item| match item {
+                Media::Carousel { images } => images
+                    .into_iter()
+                    .map(|src| Media::Image { src })
+                    .collect::<Vec<_>>(),
+                other => vec![other],
+            })
+            .collect()
+    }
+
+    pub fn media_kind(&self) -> MediaKind {
+        let slides = self.slides();
+        if slides.iter().any(|m| matches!(m, Media::Video { .. })) {
+            MediaKind::Video
+        } else if slides.len() > 1 {
+            MediaKind::Carousel
+        } else {
+            MediaKind::Photo
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Media {
+    Image {
+        src: String,
+    },
+    Video {
+        sources: Vec<String>,
+        poster: String,
+    },
+    Carousel {
+        images: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaKind {
+    Photo,
+    Video,
+    Carousel,
+}
+
+impl MediaKind {
+    pub fn icon(self) -> &'static str {
+        match self {
+            MediaKind::Photo => "fa-camera",
+            MediaKind::Video => "fa-play",
+            MediaKind::Carousel => "fa-images",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MediaKind::Photo => "Фото",
+            MediaKind::Video => "Видео",
+            MediaKind::Carousel => "Карусель",
+        }
+    }
+}
+
+/// MIME-тип видео по расширению файла.
+pub fn video_mime(src: &str) -> &'static str {
+    if src.to_lowercase().ends_with(".webm") {
+        "video/webm"
+    } else {
+        "video/mp4"
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
 pub struct TestimonialsSection {
     pub title: String,
     pub subtitle: String,
+    #[serde(default)]
+    pub layout: TestimonialsLayout,
+    #[serde(default)]
+    pub sort: TestimonialSort,
+    #[serde(default = "default_autoplay_seconds")]
+    pub autoplay_seconds: u32,
+    #[serde(default)]
+    pub max_age_months: Option<u32>,
+    #[serde(default)]
+    pub imported: Option<String>,
     pub items: Vec<Testimonial>,
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+fn default_autoplay_seconds() -> u32 {
+    6
+}
+
+impl TestimonialsSection {
+    /// Отзывы без слишком старых, если задан `max_age_months`.
+    pub fn visible_items(&self, today: CalendarDate) -> impl Iterator<Item = &Testimonial> {
+        self.items.iter().filter(move |t| {
+            self.max_age_months
+                .is_none_or(|max| t.date.months_until(today) < max as i32)
+        })
+    }
+
+    /// Средняя оценка по тем же отзывам, что видны в секции.
+    pub fn rating_summary(&self, today: CalendarDate) -> Option<RatingSummary> {
+        RatingSummary::from_ratings(self.visible_items(today).map(|t| t.rating))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TestimonialsLayout {
+    #[default]
+    Grid,
+    Carousel,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TestimonialSort {
+    #[default]
+    Date,
+    Rating,
+    Service,
+}
+
+impl TestimonialSort {
+    pub const ALL: [TestimonialSort; 3] = [
+        TestimonialSort::Date,
+        TestimonialSort::Rating,
+        TestimonialSort::Service,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TestimonialSort::Date => "Сначала новые",
+            TestimonialSort::Rating => "По оценке",
+            TestimonialSort::Service => "По услуге",
+        }
+    }
+
+    pub fn apply(self, items: &mut [&Testimonial]) {
+        match self {
+            TestimonialSort::Date => items.sort_by_key(|t| std::cmp::Reverse(t.date)),
+            TestimonialSort::Rating => {
+                items.sort_by(|a, b| b.rating.value().total_cmp(&a.rating.value()))
+            }
+            TestimonialSort::Service => items.sort_by(|a, b| a.service.cmp(&b.service)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Testimonial {
     pub name: String,
+    #[serde(default)]
     pub avatar: String,
-    pub rating: u8,
+    pub rating: Rating,
     pub text: String,
-    pub date: String,
+    pub date: CalendarDate,
     pub service: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service_ids: Vec<String>,
+}
+
+impl Testimonial {
+    /// Устойчивый ключ карточки: не меняется при смене сортировки.
+    pub fn key(&self) -> String {
+        format!("{}|{}|{}", self.name, self.date.to_iso(), self.text)
+    }
+
+    /// Подпись аватара; без явного `avatar` — инициалы из имени.
+    pub fn avatar_text(&self) -> String {
+        if self.avatar.trim().is_empty() {
+            text::initials(&self.name)
+        } else {
+            self.avatar.clone()
+        }
+    }
+}
+
+/// Файл с импортированными отзывами, который подмешивается к `testimonials.items`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ImportedTestimonials {
+    pub items: Vec<Testimonial>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct GiftCertificatesSection {
+    pub title: String,
+    pub subtitle: String,
+    pub description: String,
+    /// Номиналы в рублях.
+    #[serde(default)]
+    pub amounts: Vec<u32>,
+    /// Услуги, на которые можно подарить сертификат.
+    #[serde(default)]
+    pub service_ids: Vec<String>,
+    #[serde(default = "default_valid_months")]
+    pub valid_months: u32,
+    /// Начало кода сертификата: `YI-7KQM-3XZP-9HWD-T4NC`.
+    #[serde(default = "default_code_prefix")]
+    pub code_prefix: String,
+    /// Файл выданных сертификатов для проверки кода.
+    #[serde(default)]
+    pub codes: Option<String>,
+    /// Фон карточки сертификата.
+    #[serde(default)]
+    pub background: Option<String>,
+}
+
+fn default_valid_months() -> u32 {
+    6
+}
+
+fn default_code_prefix() -> String {
+    "YI".to_string()
+}
+
+/// Выданные сертификаты. Файл публичный, поэтому коды хранятся только в виде
+/// медленного хэша с солью: вместе с длиной кода это делает перебор бесполезным.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CertificateRegistry {
+    /// Соль хэшей; задаётся один раз при первой регистрации кода.
+    #[serde(default)]
+    pub salt: String,
+    #[serde(default = "default_hash_iterations")]
+    pub iterations: u32,
+    pub certificates: Vec<IssuedCertificate>,
+}
+
+impl Default for CertificateRegistry {
+    fn default() -> Self {
+        Self {
+            salt: String::new(),
+            iterations: default_hash_iterations(),
+            certificates: Vec::new(),
+        }
+    }
+}
+
+/// Код проверяется в браузере на главном потоке, и на слабых телефонах
+/// 100 000 итераций подвешивали страницу на секунды. От перебора защищают
+/// 80 бит случайности в коде, а не цена хэша, поэтому хватает и 10 000.
+/// Уже записанное в файл значение не меняется: иначе старые хэши не сойдутся.
+fn default_hash_iterations() -> u32 {
+    10_000
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct IssuedCertificate {
+    pub code_hash: String,
+    /// Номинал или услуга: «3 000 ₽», «Ламинирование бровей».
+    pub title: String,
+    pub valid_until: CalendarDate,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub redeemed: bool,
+}
+
+/// Код приглашения или промокод. Регистр букв при вводе не важен.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PromoCode {
+    pub code: String,
+    /// Текст баннера: «Скидка 10% по приглашению Анны».
+    pub banner: String,
+    /// Последний день действия; без него код бессрочный.
+    #[serde(default)]
+    pub valid_until: Option<CalendarDate>,
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -118,12 +783,75 @@ pub struct ContactSection {
     pub phone: String,
     pub phone_link: String,
     pub email: String,
-    pub address: String,
     pub working_hours: String,
-    pub map_url: String,
+    pub location: Location,
+    #[serde(default)]
+    pub directions: Option<Directions>,
     pub social: Vec<SocialLink>,
 }
 
+/// Где находится студия: по координатам строятся карта и ссылки на маршрут.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Location {
+    pub address: String,
+    pub lat: f64,
+    pub lon: f64,
+    #[serde(default = "default_map_zoom")]
+    pub zoom: u8,
+    /// id организации в Яндекс Картах: карта покажет её карточку с отзывами.
+    #[serde(default)]
+    pub yandex_org_id: Option<String>,
+    /// Как найти вход (Markdown).
+    #[serde(default)]
+    pub entrance: Option<String>,
+    #[serde(default)]
+    pub entrance_photo: Option<String>,
+    #[serde(default)]
+    pub entrance_photo_alt: Option<String>,
+    /// Своя картинка вместо карты, пока она не загружена или если её заблокировали;
+    /// без неё на месте карты показывается адрес.
+    #[serde(default)]
+    pub map_image: Option<String>,
+    /// Статичная карта Яндекса, если своей картинки нет. Она грузится с тех же
+    /// серверов, что и сама карта, поэтому блокируется вместе с ней.
+    #[serde(default)]
+    pub yandex_static_map: bool,
+}
+
+fn default_map_zoom() -> u8 {
+    17
+}
+
+/// Пошаговая инструкция, как дойти до студии.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Directions {
+    #[serde(default = "default_directions_title")]
+    pub title: String,
+    /// Шаги по порядку, от остановки или парковки до двери студии.
+    pub steps: Vec<DirectionStep>,
+    /// Где оставить машину (Markdown).
+    #[serde(default)]
+    pub parking: Option<String>,
+    /// Как доехать на общественном транспорте (Markdown).
+    #[serde(default)]
+    pub transport: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DirectionStep {
+    /// Фото шага; без него шаг показывается одной подписью.
+    #[serde(default)]
+    pub photo: Option<String>,
+    #[serde(default)]
+    pub alt: Option<String>,
+    /// Подпись к шагу (Markdown).
+    pub caption: String,
+}
+
+fn default_directions_title() -> String {
+    "Как нас найти".to_string()
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct SocialLink {
     pub name: String,
@@ -145,11 +873,86 @@ pub struct FooterLink {
     pub url: String,
 }
 
+/// Юридический документ в Markdown, открывается по адресу `/?page=<id>`.
+/// В тексте подставляются данные салона: `{{name}}`, `{{city}}`, `{{phone}}` и т.д.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct LegalDocument {
+    pub id: String,
+    pub title: String,
+    pub file: String,
+    /// Ссылка на документ ставится у галочки согласия в формах.
+    #[serde(default)]
+    pub form_consent: bool,
+}
+
+/// Настройки аналитики. Скрипт счётчика подключается только после согласия посетителя.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AnalyticsSection {
+    #[serde(flatten)]
+    pub provider: AnalyticsProvider,
+    /// Текст баннера согласия (Markdown).
+    #[serde(default = "default_consent_text")]
+    pub consent_text: String,
+}
+
+impl AnalyticsSection {
+    pub fn is_enabled(&self) -> bool {
+        self.provider != AnalyticsProvider::None
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "provider", rename_all = "lowercase")]
+pub enum AnalyticsProvider {
+    /// Яндекс Метрика. Вебвизор записывает действия посетителя, поэтому по умолчанию выключен.
+    Metrika {
+        counter_id: u32,
+        #[serde(default)]
+        webvisor: bool,
+    },
+    /// Plausible и совместимые с ним счётчики без cookie.
+    Plausible {
+        domain: String,
+        #[serde(default = "default_plausible_script")]
+        script: String,
+    },
+    None,
+}
+
+fn default_consent_text() -> String {
+    "Сайт использует cookie и веб-аналитику, чтобы понимать, какие разделы полезны посетителям. \
+     Подробнее — в [политике конфиденциальности](/?page=privacy)."
+        .to_string()
+}
+
+fn default_plausible_script() -> String {
+    "https://plausible.io/js/script.js".to_string()
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct SalonInfo {
     pub city: String,
     pub name: String,
     pub slogan: String,
+    /// Адрес сайта для QR-кода на прайсе; в браузере по умолчанию берётся текущий.
+    #[serde(default)]
+    pub site_url: Option<String>,
+    /// Оператор персональных данных для юридических документов: «ИП Иванова Инна Сергеевна».
+    #[serde(default)]
+    pub legal_name: Option<String>,
+    /// ИНН или ОГРНИП оператора.
+    #[serde(default)]
+    pub legal_id: Option<String>,
+}
+
+impl SalonInfo {
+    /// Оператор персональных данных, если он указан.
+    pub fn legal_name(&self) -> Option<&str> {
+        self.legal_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -164,3 +967,169 @@ pub struct Contacts {
     pub address: String,
     pub working_hours: String,
 }
+
+#[cfg(all(test, feature = "yaml"))]
+mod tests {
+    use super::{Content, LegalDocument, Media, MediaKind, PortfolioItem, PromoCode};
+    use crate::content_loader::load_content_file;
+
+    fn content() -> Content {
+        load_content_file("static/content.yaml").expect("static/content.yaml should load")
+    }
+
+    #[test]
+    fn content_references_known_services() {
+        assert_eq!(content().repair(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unknown_service_references_are_dropped() {
+        let mut content = content();
+        content.portfolio.items[0].service_ids = vec!["no-such-service".to_string()];
+        let title = content.portfolio.items[0].title.clone();
+
+        assert_eq!(
+            content.repair(),
+            vec![format!(
+                "Portfolio item \"{}\" references unknown service id: no-such-service",
+                title
+            )]
+        );
+        assert!(content.portfolio.items[0].service_ids.is_empty());
+    }
+
+    #[test]
+    fn content_images_have_alt() {
+        let missing = content().missing_alt();
+        assert!(
+            missing.is_empty(),
+            "Add alt text for: {}",
+            missing.join(", ")
+        );
+    }
+
+    #[test]
+    fn missing_alt_is_reported() {
+        let mut content = content();
+        content.about.image_alt = Some("  ".to_string());
+        content.portfolio.items[0].alt = None;
+        let title = content.portfolio.items[0].title.clone();
+
+        assert_eq!(
+            content.missing_alt(),
+            vec![
+                format!("about: {}", content.about.image),
+                format!("portfolio: {}", title),
+            ]
+        );
+    }
+
+    fn work(media: &str) -> PortfolioItem {
+        let yaml = format!("category: Ногти\ntitle: Работа\ndescription: ''\n{media}");
+        serde_yaml::from_str(&yaml).expect("portfolio item should parse")
+    }
+
+    #[test]
+    fn legacy_image_is_a_single_photo() {
+        let item = work("image: /static/images/work.jpg");
+        assert_eq!(
+            item.slides(),
+            vec![Media::Image {
+                src: "/static/images/work.jpg".to_string()
+            }]
+        );
+        assert_eq!(item.media_kind(), MediaKind::Photo);
+
+        assert!(work("").slides().is_empty());
+    }
+
+    #[test]
+    fn media_takes_precedence_over_image() {
+        let item = work(
+            "image: /static/images/old.jpg\n\
+             media: [{ type: image, src: /static/images/new.jpg }]",
+        );
+        assert_eq!(
+            item.slides(),
+            vec![Media::Image {
+                src: "/static/images/new.jpg".to_string()
+            }]
+        );
+        assert_eq!(item.media_kind(), MediaKind::Photo);
+    }
+
+    #[test]
+    fn media_entries_are_classified() {
+        let video = work(
+            "media: [{ type: video, sources: [/static/video/work.webm, /static/video/work.mp4], \
+             poster: /static/images/work.jpg }]",
+        );
+        assert!(matches!(video.slides()[..], [Media::Video { .. }]));
+        assert_eq!(video.media_kind(), MediaKind::Video);
+
+        let carousel = work("media: [{ type: carousel, images: [/a.jpg, /b.jpg] }]");
+        assert_eq!(
+            carousel.slides(),
+            vec![
+                Media::Image {
+                    src: "/a.jpg".to_string()
+                },
+                Media::Image {
+                    src: "/b.jpg".to_string()
+                },
+            ]
+        );
+        assert_eq!(carousel.media_kind(), MediaKind::Carousel);
+
+        let mixed = work(
+            "media:\n\
+             - { type: image, src: /a.jpg }\n\
+             - { type: video, sources: [/a.mp4], poster: /a.jpg }",
+        );
+        assert_eq!(mixed.slides().len(), 2);
+        assert_eq!(mixed.media_kind(), MediaKind::Video);
+    }
+
+    #[test]
+    fn empty_and_duplicate_promo_codes_are_dropped() {
+        let promo = |code: &str| PromoCode {
+            code: code.to_string(),
+            banner: format!("Баннер {}", code),
+            valid_until: None,
+        };
+        let mut content = content();
+        content.promo_codes = vec![
+            promo("FRIEND"),
+            promo(" friend "),
+            promo("  "),
+            promo("VIP"),
+        ];
+
+        assert!(content.validate().is_err());
+        let issues = content.repair();
+        let codes = content
+            .promo_codes
+            .iter()
+            .map(|promo| promo.code.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, ["FRIEND", "VIP"]);
+        assert_eq!(issues.len(), 2);
+    }
+
+    #[test]
+    fn legal_documents_need_an_operator() {
+        let mut content = content();
+        content.legal = vec![LegalDocument {
+            id: "privacy".to_string(),
+            title: "Политика конфиденциальности".to_string(),
+            file: "/static/legal/privacy.md".to_string(),
+            form_consent: true,
+        }];
+        content.info.legal_name = Some(" ".to_string());
+        assert!(content.validate().is_err());
+        assert_eq!(content.clone().repair().len(), 1);
+
+        content.info.legal_name = Some("ИП Иванова Инна Сергеевна".to_string());
+        assert!(content.validate().is_ok());
+    }
+}
@@ -0,0 +1,266 @@
+use crate::text::plural_ru;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+
+const MONTHS_GENITIVE: [&str; 12] = [
+    "января",
+    "февраля",
+    "марта",
+    "апреля",
+    "мая",
+    "июня",
+    "июля",
+    "августа",
+    "сентября",
+    "октября",
+    "ноября",
+    "декабря",
+];
+
+/// Календарная дата без времени. В YAML принимается ISO-8601 (`2024-10-15`)
+/// или прежний формат с названием месяца (`15 октября 2024`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CalendarDate {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl CalendarDate {
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, String> {
+        if !(1..=12).contains(&month) {
+            return Err(format!("Invalid month: {}", month));
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(format!("Invalid day {} for {}-{:02}", day, year, month));
+        }
+        Ok(Self { year, month, day })
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        parse_iso(value)
+            .or_else(|| parse_dotted(value))
+            .or_else(|| parse_russian(value))
+            .ok_or_else(|| format!("Unrecognized date: \"{}\"", value))?
+    }
+
+    /// Текущая дата по часам браузера.
+    pub fn today() -> Self {
+        let now = js_sys::Date::new_0();
+        Self {
+            year: now.get_full_year() as i32,
+            month: now.get_month() + 1,
+            day: now.get_date(),
+        }
+    }
+
+    /// Дата по Unix-времени в секундах (UTC).
+    #[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+    pub fn from_unix(seconds: i64) -> Self {
+        Self::from_days_since_epoch(seconds.div_euclid(86_400))
+    }
+
+    /// Та же дата через `months` месяцев; 31-е число сдвигается на конец короткого месяца.
+    pub fn add_months(self, months: u32) -> Self {
+        let total = self.month - 1 + months;
+        let year = self.year + (total / 12) as i32;
+        let month = total % 12 + 1;
+        Self {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Self::from_days_since_epoch(self.days_from_epoch() + days)
+    }
+
+    pub fn to_iso(self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// «15 октября 2024».
+    pub fn format_ru(self) -> String {
+        format!(
+            "{} {} {}",
+            self.day,
+            MONTHS_GENITIVE[self.month as usize - 1],
+            self.year
+        )
+    }
+
+    /// Число полных месяцев от этой даты до `later`.
+    pub fn months_until(self, later: Self) -> i32 {
+        let mut months = (later.year - self.year) * 12 + later.month as i32 - self.month as i32;
+        if later.day < self.day {
+            months -= 1;
+        }
+        months
+    }
+
+    pub fn days_until(self, later: Self) -> i64 {
+        later.days_from_epoch() - self.days_from_epoch()
+    }
+
+    /// Относительная дата: «вчера», «3 недели назад», «2 месяца назад».
+    pub fn relative_ru(self, today: Self) -> String {
+        let days = self.days_until(today);
+        let months = self.months_until(today);
+
+        match days {
+            i64::MIN..=0 => "сегодня".to_string(),
+            1 => "вчера".to_string(),
+            2..=6 => ago(days as usize, "день", "дня", "дней"),
+            7..=30 if months < 1 => ago(days as usize / 7, "неделю", "недели", "недель"),
+            _ if months < 12 => ago(months.max(1) as usize, "месяц", "месяца", "месяцев"),
+            _ => ago(months as usize / 12, "год", "года", "лет"),
+        }
+    }
+
+    /// Дни от 1970-01-01 (алгоритм days_from_civil Говарда Хиннанта).
+    fn days_from_epoch(self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Обратное преобразование (алгоритм civil_from_days).
+    fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+}
+
+impl Serialize for CalendarDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_iso())
+    }
+}
+
+impl TryFrom<String> for CalendarDate {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format_ru())
+    }
+}
+
+fn ago(n: usize, one: &str, few: &str, many: &str) -> String {
+    if n == 1 {
+        format!("{} назад", one)
+    } else {
+        format!("{} {} назад", n, plural_ru(n, one, few, many))
+    }
+}
+
+fn parse_iso(value: &str) -> Option<Result<CalendarDate, String>> {
+    let date = value.split('T').next()?;
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Some(CalendarDate::new(year, month, day))
+}
+
+/// `15.10.2024`, в том числе со временем после даты: так даты выгружают Excel и CRM.
+fn parse_dotted(value: &str) -> Option<Result<CalendarDate, String>> {
+    let date = value.split_whitespace().next()?;
+    let mut parts = date.split('.');
+    let day = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let year = parts.next().filter(|y| y.len() == 4)?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(CalendarDate::new(year, month, day))
+}
+
+fn parse_russian(value: &str) -> Option<Result<CalendarDate, String>> {
+    let mut parts = value.split_whitespace();
+    let day = parts.next()?.parse().ok()?;
+    let month_name = parts.next()?.to_lowercase();
+    let year = parts.next()?.trim_end_matches(['г', '.']).parse().ok()?;
+    if parts.next().is_some_and(|rest| !rest.starts_with('г')) {
+        return None;
+    }
+
+    let month = MONTHS_GENITIVE
+        .iter()
+        .position(|name| *name == month_name)?;
+    Some(CalendarDate::new(year, month as u32 + 1, day))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_iso_and_russian_dates() {
+        let date = CalendarDate::new(2024, 10, 15).unwrap();
+        assert_eq!(CalendarDate::parse("2024-10-15"), Ok(date));
+        assert_eq!(CalendarDate::parse(" 2024-10-15T12:00:00 "), Ok(date));
+        assert_eq!(CalendarDate::parse("15 октября 2024"), Ok(date));
+        assert_eq!(CalendarDate::parse("15 Октября 2024 г."), Ok(date));
+        assert_eq!(CalendarDate::parse("15.10.2024"), Ok(date));
+        assert_eq!(CalendarDate::parse("15.10.2024 18:30"), Ok(date));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for value in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-00-10",
+            "2024-04-31",
+            "2024-10-00",
+            "31 апреля 2024",
+            "15 октябрь 2024",
+            "31.04.2024",
+            "15.10.24",
+            "15.10",
+            "",
+            "вчера",
+        ] {
+            assert!(CalendarDate::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(CalendarDate::new(2024, 2, 29).is_ok());
+        assert!(CalendarDate::new(2000, 2, 29).is_ok());
+        assert!(CalendarDate::new(1900, 2, 29).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::data::{CertificateRegistry, IssuedCertificate, ServicesSection};
+use crate::date::CalendarDate;
+use crate::pricing::format_rub;
+use sha2::Sha256;
+
+/// Символы кода без похожих друг на друга 0/O и 1/I: код диктуют по телефону.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_GROUP: usize = 4;
+const CODE_GROUPS: usize = 4;
+/// Случайных символов в коде: 80 бит, перебором по хэшам из публичного файла не подобрать.
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+pub const CODE_SYMBOLS: usize = CODE_GROUP * CODE_GROUPS;
+
+/// Что дарят: номинал в рублях или услугу по её id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CertificateValue {
+    Amount(u32),
+    Service(String),
+}
+
+impl CertificateValue {
+    /// «3 000 ₽» или «Ламинирование бровей (2500 ₽)».
+    pub fn title(&self, services: &ServicesSection) -> String {
+        match self {
+            CertificateValue::Amount(amount) => format_rub(*amount),
+            CertificateValue::Service(id) => services.find(id).map_or_else(
+                || id.clone(),
+                |item| format!("{} ({})", item.name, item.price),
+            ),
+        }
+    }
+}
+
+/// Результат проверки кода сертификата.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CertificateStatus {
+    Valid(IssuedCertificate),
+    Expired(IssuedCertificate),
+    Redeemed(IssuedCertificate),
+    NotFound,
+}
+
+/// Новый код вида `YI-7KQM-3XZP-9HWD-T4NC` из криптографически случайных байт браузера.
+pub fn generate_code(prefix: &str) -> Result<String, String> {
+    let mut random = [0u8; CODE_GROUP * CODE_GROUPS];
+    web_sys::window()
+        .ok_or("No window")?
+        .crypto()
+        .map_err(|e| format!("Crypto API is unavailable: {:?}", e))?
+        .get_random_values_with_u8_array(&mut random)
+        .map_err(|e| format!("Failed to generate random code: {:?}", e))?;
+    Ok(format_code(prefix, &random))
+}
+
+pub fn format_code(prefix: &str, random: &[u8]) -> String {
+    let symbols = random
+        .iter()
+        .map(|byte| CODE_ALPHABET[*byte as usize % CODE_ALPHABET.len()] as char)
+        .collect::<Vec<_>>();
+    let groups = symbols
+        .chunks(CODE_GROUP)
+        .map(|group| group.iter().collect::<String>());
+    std::iter::once(prefix.to_uppercase())
+        .chain(groups)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Код в каноническом виде: регистр, пробелы и вид тире при вводе не важны.
+pub fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Число случайных символов кода после префикса `prefix`.
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+pub fn random_symbols(code: &str, prefix: &str) -> usize {
+    normalize_code(code)
+        .strip_prefix(&normalize_code(prefix))
+        .map_or(0, str::len)
+}
+
+/// PBKDF2-HMAC-SHA256 нормализованного кода с солью файла выданных сертификатов,
+/// в шестнадцатеричном виде.
+pub fn code_hash(code: &str, salt: &str, iterations: u32) -> String {
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(
+        normalize_code(code).as_bytes(),
+        salt.as_bytes(),
+        iterations,
+        &mut hash,
+    );
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+impl CertificateRegistry {
+    pub fn hash(&self, code: &str) -> String {
+        code_hash(code, &self.salt, self.iterations)
+    }
+
+    pub fn find(&self, code: &str) -> Option<&IssuedCertificate> {
+        let hash = self.hash(code);
+        self.certificates
+            .iter()
+            .find(|certificate| certificate.code_hash == hash)
+    }
+
+    pub fn check(&self, code: &str, today: CalendarDate) -> CertificateStatus {
+        match self.find(code) {
+            None => CertificateStatus::NotFound,
+            Some(certificate) if certificate.redeemed => {
+                CertificateStatus::Redeemed(certificate.clone())
+            }
+            Some(certificate) if certificate.valid_until < today => {
+                CertificateStatus::Expired(certificate.clone())
+            }
+            Some(certificate) => CertificateStatus::Valid(certificate.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = "YI-7KQM-3XZP-9HWD-T4NC";
+
+    fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+        CalendarDate::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn formats_codes_in_groups_without_ambiguous_symbols() {
+        let random = (0..16).collect::<Vec<u8>>();
+        assert_eq!(format_code("yi", &random), "YI-ABCD-EFGH-JKLM-NPQR");
+
+        // Байт берётся по модулю алфавита
+        let random = [31, 32, 255, 64].repeat(4);
+        assert_eq!(format_code("YI", &random), "YI-9A9A-9A9A-9A9A-9A9A");
+
+        let random = (0..=255).collect::<Vec<u8>>();
+        let code = format_code("", &random);
+        assert!(!code.contains(['0', 'O', '1', 'I']));
+    }
+
+    #[test]
+    fn normalizes_typed_codes() {
+        assert_eq!(
+            normalize_code(" yi–7kqm 3xzp-9hwd-t4nc "),
+            "YI7KQM3XZP9HWDT4NC"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn counts_random_symbols_after_prefix() {
+        assert_eq!(random_symbols(CODE, "YI"), 16);
+        assert_eq!(random_symbols("yi-7kqm", "YI"), 4);
+        assert_eq!(random_symbols(CODE, "GIFT"), 0);
+    }
+
+    #[test]
+    fn hashes_normalized_code_with_pbkdf2() {
+        // hashlib.pbkdf2_hmac("sha256", b"YI7KQM3XZP9HWDT4NC", b"salt", 1000)
+        let expected = "32a37aa08a5aecca82b2f40238cc89f495f37f8063d99dc9afe35d4d4527ddb3";
+        assert_eq!(code_hash(CODE, "salt", 1000), expected);
+        assert_eq!(code_hash("yi 7kqm 3xzp 9hwd t4nc", "salt", 1000), expected);
+        assert_ne!(code_hash(CODE, "pepper", 1000), expected);
+        assert_ne!(code_hash(CODE, "salt", 1001), expected);
+    }
+
+    #[test]
+    fn checks_codes_against_the_registry() {
+        let mut registry = CertificateRegistry {
+            salt: "salt".to_string(),
+            iterations: 1000,
+            certificates: Vec::new(),
+        };
+        let issue =
+            |registry: &CertificateRegistry, code: &str, redeemed: bool| IssuedCertificate {
+                code_hash: registry.hash(code),
+                title: "3 000 ₽".to_string(),
+                valid_until: date(2024, 12, 31),
+                redeemed,
+            };
+        let valid = issue(&registry, CODE, false);
+        let redeemed = issue(&registry, "YI-AAAA-BBBB-CCCC-DDDD", true);
+        registry.certificates = vec![valid.clone(), redeemed.clone()];
+
+        let today = date(2024, 12, 31);
+        assert_eq!(
+            registry.check("yi7kqm3xzp9hwdt4nc", today),
+            CertificateStatus::Valid(valid.clone())
+        );
+        assert_eq!(
+            registry.check(CODE, date(2025, 1, 1)),
+            CertificateStatus::Expired(valid)
+        );
+        assert_eq!(
+            registry.check("YI-AAAA-BBBB-CCCC-DDDD", today),
+            CertificateStatus::Redeemed(redeemed)
+        );
+        assert_eq!(
+            registry.check("YI-7KQM-3XZP-9HWD-T4ND", today),
+            CertificateStatus::NotFound
+        );
+    }
+}
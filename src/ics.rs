@@ -0,0 +1,209 @@
+use crate::date::CalendarDate;
+use crate::vcard::{escape, fold_lines};
+
+/// Напоминание о записи за два часа.
+const REMINDER: &str = "-PT2H";
+
+/// Часовой пояс салона (Сочи): время записи не зависит от пояса телефона клиента.
+const TIMEZONE: &str = "Europe/Moscow";
+
+/// Описание пояса для календарей, которые не знают его по имени. С 2014 года
+/// в Москве круглый год UTC+3, без перехода на летнее время.
+const VTIMEZONE: [&str; 9] = [
+    "BEGIN:VTIMEZONE",
+    "TZID:Europe/Moscow",
+    "BEGIN:STANDARD",
+    "DTSTART:19700101T000000",
+    "TZOFFSETFROM:+0300",
+    "TZOFFSETTO:+0300",
+    "TZNAME:MSK",
+    "END:STANDARD",
+    "END:VTIMEZONE",
+];
+
+/// Длительность визита, если у услуг она не указана.
+pub const DEFAULT_MINUTES: u32 = 60;
+
+/// Локальные дата и время записи.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalDateTime {
+    pub date: CalendarDate,
+    /// Минуты от начала суток.
+    pub minutes: u32,
+}
+
+impl LocalDateTime {
+    /// Значение поля `<input type="datetime-local">`: `2024-10-15T14:30`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (date, time) = value
+            .split_once('T')
+            .ok_or_else(|| format!("Invalid date and time: \"{}\"", value))?;
+        let (hours, minutes) = time
+            .split_once(':')
+            .and_then(|(hours, rest)| {
+                Some((
+                    hours.parse::<u32>().ok()?,
+                    rest.get(..2)?.parse::<u32>().ok()?,
+                ))
+            })
+            .filter(|(hours, minutes)| *hours < 24 && *minutes < 60)
+            .ok_or_else(|| format!("Invalid time: \"{}\"", time))?;
+        Ok(Self {
+            date: CalendarDate::parse(date)?,
+            minutes: hours * 60 + minutes,
+        })
+    }
+
+    pub fn add_minutes(self, minutes: u32) -> Self {
+        let total = self.minutes + minutes;
+        Self {
+            date: self.date.add_days(i64::from(total / (24 * 60))),
+            minutes: total % (24 * 60),
+        }
+    }
+
+    /// Местное время салона; пояс указывается в параметре `TZID` поля.
+    fn to_ics(self) -> String {
+        format!(
+            "{}T{:02}{:02}00",
+            self.date.to_iso().replace('-', ""),
+            self.minutes / 60,
+            self.minutes % 60
+        )
+    }
+}
+
+/// Событие записи для календаря.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Appointment {
+    pub title: String,
+    pub description: String,
+    pub location: String,
+    pub start: LocalDateTime,
+    pub minutes: u32,
+}
+
+impl Appointment {
+    /// Файл `.ics` с одним событием и напоминанием. `stamp` — время создания
+    /// в UTC вида `20241015T120000Z`.
+    pub fn to_ics(&self, stamp: &str) -> String {
+        let start = self.start.to_ics();
+        let header = [
+            "BEGIN:VCALENDAR",
+            "VERSION:2.0",
+            "PRODID:-//yainna//booking//RU",
+            "CALSCALE:GREGORIAN",
+            "METHOD:PUBLISH",
+        ];
+        let event = [
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-{}@yainna", start, stamp),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;TZID={}:{}", TIMEZONE, start),
+            format!(
+                "DTEND;TZID={}:{}",
+                TIMEZONE,
+                self.start.add_minutes(self.minutes).to_ics()
+            ),
+            format!("SUMMARY:{}", escape(&self.title)),
+            format!("DESCRIPTION:{}", escape(&self.description)),
+            format!("LOCATION:{}", escape(&self.location)),
+            "BEGIN:VALARM".to_string(),
+            "ACTION:DISPLAY".to_string(),
+            format!("DESCRIPTION:{}", escape(&self.title)),
+            format!("TRIGGER:{}", REMINDER),
+            "END:VALARM".to_string(),
+            "END:VEVENT".to_string(),
+            "END:VCALENDAR".to_string(),
+        ];
+        let lines = header
+            .into_iter()
+            .chain(VTIMEZONE)
+            .map(str::to_string)
+            .chain(event)
+            .collect::<Vec<_>>();
+        fold_lines(&lines)
+    }
+}
+
+/// Текущее время UTC в формате iCalendar.
+pub fn utc_stamp() -> String {
+    // toISOString: 2024-10-15T12:00:00.000Z
+    let iso = String::from(js_sys::Date::new_0().to_iso_string());
+    let compact = iso
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'T')
+        .take(15)
+        .collect::<String>();
+    format!("{}Z", compact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn appointment(title: &str, description: &str) -> Appointment {
+        Appointment {
+            title: title.to_string(),
+            description: description.to_string(),
+            location: "Сочи, ул. Морская, 1".to_string(),
+            start: LocalDateTime::parse("2024-10-15T23:30").unwrap(),
+            minutes: 90,
+        }
+    }
+
+    /// Склеивает перенесённые строки обратно, как это делает календарь.
+    fn unfold(ics: &str) -> String {
+        ics.replace("\r\n ", "")
+    }
+
+    #[test]
+    fn rejects_invalid_date_and_time() {
+        for value in [
+            "2024-10-15",
+            "2024-10-15T24:00",
+            "2024-10-15T12:60",
+            "2024-02-30T10:00",
+            "2024-10-15T9",
+        ] {
+            assert!(LocalDateTime::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn end_moves_to_next_day() {
+        let ics = appointment("Макияж", "").to_ics("20241015T120000Z");
+        assert!(ics.contains("DTSTART;TZID=Europe/Moscow:20241015T233000\r\n"));
+        assert!(ics.contains("DTEND;TZID=Europe/Moscow:20241016T010000\r\n"));
+    }
+
+    #[test]
+    fn defines_the_salon_timezone_before_the_event() {
+        let ics = appointment("Макияж", "").to_ics("20241015T120000Z");
+        let timezone = ics
+            .find("BEGIN:VTIMEZONE\r\nTZID:Europe/Moscow\r\n")
+            .unwrap();
+        assert!(timezone < ics.find("BEGIN:VEVENT").unwrap());
+        assert!(ics.contains("TZOFFSETTO:+0300\r\n"));
+        assert!(ics.contains("DTSTAMP:20241015T120000Z\r\n"));
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        let ics = unfold(&appointment("Макияж, брови; ресницы", "Первая\nвторая").to_ics("stamp"));
+        assert!(ics.contains("SUMMARY:Макияж\\, брови\\; ресницы\r\n"));
+        assert!(ics.contains("DESCRIPTION:Первая\\nвторая\r\n"));
+        assert!(ics.contains("LOCATION:Сочи\\, ул. Морская\\, 1\r\n"));
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let description = "Вечерний макияж, укладка; ".repeat(10);
+        let ics = appointment("Макияж", &description).to_ics("stamp");
+        assert!(
+            ics.lines()
+                .all(|line| line.trim_end_matches('\r').len() <= 75)
+        );
+        assert!(unfold(&ics).contains(&format!("DESCRIPTION:{}\r\n", escape(&description))));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::data::{Contacts, LegalDocument, SalonInfo};
+use crate::url_query;
+
+/// Параметр адреса страницы документа: `/?page=privacy`.
+pub const PAGE_PARAM: &str = "page";
+
+pub fn page_url(document: &LegalDocument) -> String {
+    format!("/?{}={}", PAGE_PARAM, document.id)
+}
+
+/// Документы, согласие с которыми отмечают галочкой в форме заявки.
+pub fn consent_documents(documents: &[LegalDocument]) -> Vec<&LegalDocument> {
+    documents
+        .iter()
+        .filter(|document| document.form_consent)
+        .collect()
+}
+
+/// Заявку можно отправить, если галочки нет или она отмечена. Браузерная
+/// проверка `required` не годится: она не даёт показать своё сообщение.
+pub fn may_submit(documents: &[LegalDocument], agreed: bool) -> bool {
+    agreed || consent_documents(documents).is_empty()
+}
+
+/// Подставляет в текст документа данные салона. Неизвестные `{{поля}}` остаются
+/// как есть, чтобы их было видно при проверке текста. Без `info.legal_name`
+/// документ не собирается: оператором данных не может быть название салона.
+pub fn fill(template: &str, info: &SalonInfo, contacts: &Contacts) -> Result<String, String> {
+    let legal_name = info
+        .legal_name()
+        .ok_or("info.legal_name is not set, legal documents have no operator")?;
+    let site_url = info
+        .site_url
+        .clone()
+        .or_else(url_query::page_url)
+        .unwrap_or_default();
+    // «ИП Иванова Инна Сергеевна, ИНН 000000000000»
+    let operator = match info.legal_id.as_deref() {
+        Some(legal_id) => format!("{}, {}", legal_name, legal_id),
+        None => legal_name.to_string(),
+    };
+    let values = [
+        ("name", info.name.as_str()),
+        ("city", info.city.as_str()),
+        ("legal_name", legal_name),
+        ("operator", operator.as_str()),
+        ("legal_id", info.legal_id.as_deref().unwrap_or_default()),
+        ("site_url", site_url.as_str()),
+        ("phone", contacts.phone.as_str()),
+        ("email", contacts.email.as_str()),
+        ("address", contacts.address.as_str()),
+    ];
+
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let key = after[..end].trim();
+        match values.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) => result.push_str(value),
+            None => result.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> SalonInfo {
+        SalonInfo {
+            city: "Сочи".to_string(),
+            name: "Я Инна".to_string(),
+            slogan: String::new(),
+            site_url: Some("https://example.ru".to_string()),
+            legal_name: Some("ИП Иванова Инна Сергеевна".to_string()),
+            legal_id: Some("ИНН 000000000000".to_string()),
+        }
+    }
+
+    fn contacts() -> Contacts {
+        Contacts {
+            link: String::new(),
+            phone: "+7 (900) 000-00-00".to_string(),
+            telegram: String::new(),
+            whatsapp: String::new(),
+            instagram: String::new(),
+            vk: String::new(),
+            email: "mail@example.ru".to_string(),
+            address: "г. Сочи".to_string(),
+            working_hours: String::new(),
+        }
+    }
+
+    #[test]
+    fn substitutes_known_fields() {
+        let text = fill(
+            "{{operator}} ({{ name }}, {{city}}) — {{site_url}}, {{phone}}, {{email}}",
+            &info(),
+            &contacts(),
+        );
+        assert_eq!(
+            text.unwrap(),
+            "ИП Иванова Инна Сергеевна, ИНН 000000000000 (Я Инна, Сочи) — \
+             https://example.ru, +7 (900) 000-00-00, mail@example.ru"
+        );
+
+        let mut info = info();
+        info.legal_id = None;
+        assert_eq!(
+            fill("{{operator}}", &info, &contacts()).unwrap(),
+            "ИП Иванова Инна Сергеевна"
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_fields_and_unclosed_braces() {
+        let info = info();
+        assert_eq!(
+            fill("{{unknown}} и {{name}}", &info, &contacts()).unwrap(),
+            "{{unknown}} и Я Инна"
+        );
+        assert_eq!(
+            fill("{{name}}: {{city", &info, &contacts()).unwrap(),
+            "Я Инна: {{city"
+        );
+        assert_eq!(fill("}} {{", &info, &contacts()).unwrap(), "}} {{");
+    }
+
+    #[test]
+    fn requires_an_operator() {
+        let mut info = info();
+        info.legal_name = Some("  ".to_string());
+        assert!(fill("{{operator}}", &info, &contacts()).is_err());
+        info.legal_name = None;
+        assert!(fill("{{name}}", &info, &contacts()).is_err());
+    }
+
+    #[test]
+    fn consent_is_needed_only_for_form_documents() {
+        let document = |id: &str, form_consent: bool| LegalDocument {
+            id: id.to_string(),
+            title: id.to_string(),
+            file: format!("/static/legal/{}.md", id),
+            form_consent,
+        };
+        let documents = vec![document("privacy", true), document("offer", false)];
+
+        let ids = consent_documents(&documents)
+            .iter()
+            .map(|document| document.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["privacy"]);
+        assert!(!may_submit(&documents, false));
+        assert!(may_submit(&documents, true));
+
+        assert!(may_submit(&documents[1..], false));
+        assert!(may_submit(&[], false));
+    }
+}
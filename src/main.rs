@@ -1,20 +1,125 @@
 use data::{Button, Content};
-use gloo_net::http::Request;
+use portfolio_filter::PortfolioFilter;
+use sections::calculator::render_calculator_summary;
+use sections::directions::DirectionsGuide;
+use sections::faq::FaqSection;
+use sections::gift_certificates::GiftCertificatesSection;
+use sections::legal_page::LegalPage;
+use sections::map_embed::MapEmbed;
+use sections::service_details::ServiceDetailsCard;
+use sections::testimonial_card::TestimonialCard;
+use sections::testimonial_carousel::TestimonialCarousel;
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
+mod analytics;
+mod booking;
+#[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+mod cli;
+mod content_format;
+mod content_loader;
 mod data;
+mod date;
+mod gift_certificate;
+mod ics;
+mod legal;
+mod maps;
+mod markdown;
+mod portfolio_filter;
+mod pricing;
+mod promo;
+mod pwa;
+mod qr;
+mod rating;
+mod sections;
+mod text;
+mod url_query;
+mod vcard;
+mod viewport;
+
+const ALL_FILTER: &str = "Все";
+const SERVICE_PARAM: &str = "service";
+const PRINT_PARAM: &str = "print";
+const PRINT_PRICES: &str = "prices";
 
 #[function_component(App)]
 fn app() -> Html {
-    let content = use_state(|| None::<Content>);
+    let hero = use_state(|| None::<data::HeroSection>);
+    let page_content = use_reducer(PageContent::default);
+    let promo = use_state(|| None::<data::PromoCode>);
+    let consent_pending = use_state(|| false);
+    let selected_service = use_state(|| url_query::get(SERVICE_PARAM));
+    let print_prices = use_state(|| url_query::get(PRINT_PARAM).as_deref() == Some(PRINT_PRICES));
+    let page = use_state(|| url_query::get(legal::PAGE_PARAM));
 
     {
-        let content = content.clone();
+        let selected_service = selected_service.clone();
+        use_effect_with((*selected_service).clone(), move |service| {
+            url_query::set_all(&[(SERVICE_PARAM, service.as_deref())]);
+            || ()
+        });
+    }
+
+    let on_select_service = {
+        let selected_service = selected_service.clone();
+        Callback::from(move |id: Option<String>| {
+            if let Some(service_id) = id.clone() {
+                analytics::reach_goal(&analytics::Goal::ServiceFilter { service_id });
+            }
+            selected_service.set(id)
+        })
+    };
+
+    {
+        let hero = hero.clone();
+        let page_content = page_content.dispatcher();
+        let promo = promo.clone();
+        let consent_pending = consent_pending.clone();
         use_effect_with((), move |_| {
+            analytics::capture_attribution();
+            spawn_local(async {
+                if let Err(e) = pwa::register_service_worker().await {
+                    gloo_console::warn!(e);
+                }
+            });
             spawn_local(async move {
-                match load_content().await {
-                    Ok(data) => content.set(Some(data)),
+                let mut root = match content_loader::fetch_root(content_loader::CONTENT_PATH).await
+                {
+                    Ok(root) => root,
+                    Err(e) => {
+                        gloo_console::error!("Failed to load content:", &e);
+                        return;
+                    }
+                };
+
+                // Первый экран показываем сразу, остальные секции догружаются следом
+                match content_loader::load_section(&mut root, "hero").await {
+                    Ok(data) => hero.set(Some(data)),
+                    Err(e) => gloo_console::error!("Failed to load hero section:", &e),
+                }
+
+                // Ссылка на вопрос должна открыть его сразу, без прокрутки до раздела
+                let eager: &[&str] = match url_query::hash() {
+                    Some(hash) if hash.starts_with(data::FAQ_ANCHOR_PREFIX) => &["faq"],
+                    _ => &[],
+                };
+                let pending = content_loader::take_lazy_sections(&mut root, eager);
+
+                match content_loader::load_content(root).await {
+                    Ok(data) => {
+                        if let Some(settings) = data.analytics.as_ref() {
+                            analytics::configure(&settings.provider);
+                            consent_pending
+                                .set(settings.is_enabled() && analytics::consent().is_none());
+                        }
+                        promo.set(promo::resolve(
+                            &data.promo_codes,
+                            date::CalendarDate::today(),
+                        ));
+                        page_content.dispatch(PageAction::Loaded(Box::new(data), pending));
+                    }
                     Err(e) => {
                         gloo_console::error!("Failed to load content:", &e);
                     }
@@ -24,16 +129,141 @@ fn app() -> Html {
         });
     }
 
-    match (*content).as_ref() {
+    let on_consent = {
+        let consent_pending = consent_pending.clone();
+        Callback::from(move |consent: analytics::Consent| {
+            analytics::set_consent(consent);
+            consent_pending.set(false);
+        })
+    };
+
+    let on_cookie_settings = {
+        let consent_pending = consent_pending.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            consent_pending.set(true);
+        })
+    };
+
+    let on_lazy_section = {
+        let page_content = page_content.dispatcher();
+        Callback::from(move |(key, section)| {
+            page_content.dispatch(PageAction::Section(key, section))
+        })
+    };
+    let lazy = |key: &str, anchor: &str, section: Html| match page_content.pending.get(key) {
+        Some(value) => html! {
+            <LazySectionLoader
+                name={key.to_string()}
+                anchor={anchor.to_string()}
+                value={value.clone()}
+                on_load={on_lazy_section.clone()}
+            />
+        },
+        None if page_content.failed.contains(key) => html! {},
+        None => section,
+    };
+
+    let legal_page = page_content.content.as_ref().and_then(|data| {
+        let id = (*page).as_deref()?;
+        data.legal.iter().find(|document| document.id == id)
+    });
+
+    match page_content.content.as_ref() {
+        Some(data) if legal_page.is_some() => html! {
+            { for legal_page.cloned().map(|document| html! {
+                <LegalPage
+                    document={document}
+                    info={data.info.clone()}
+                    contacts={data.contacts.clone()}
+                />
+            }) }
+        },
+        Some(data) if *print_prices => html! {
+            <PriceSheet
+                services={data.services.clone()}
+                info={data.info.clone()}
+                contacts={data.contacts.clone()}
+            />
+        },
         Some(data) => html! {
             <>
-                <HeroSection hero={data.hero.clone()} />
-                <AboutSection about={data.about.clone()} />
-                <ServicesSection services={data.services.clone()} contacts={data.contacts.clone()} />
-                <PortfolioSection portfolio={data.portfolio.clone()} />
-                <TestimonialsSection testimonials={data.testimonials.clone()} contacts={data.contacts.clone()} />
-                <ContactSection contact={data.contact.clone()} />
-                <Footer footer={data.footer.clone()} contacts={data.contacts.clone()} />
+                <a
+                    href="#main"
+                    class="sr-only focus:not-sr-only focus:fixed focus:top-4 focus:left-4 focus:z-[100] focus:bg-white focus:text-amber-900 focus:px-6 focus:py-3 focus:rounded-full focus:shadow-xl font-body font-semibold"
+                >
+                    { "Перейти к содержанию" }
+                </a>
+                { for (*promo).clone().map(|promo| html! { <PromoBanner promo={promo} /> }) }
+                <main id="main" tabindex="-1" class="focus:outline-none">
+                    <HeroSection hero={data.hero.clone()} />
+                    <AboutSection about={data.about.clone()} />
+                    <ServicesSection
+                        services={data.services.clone()}
+                        info={data.info.clone()}
+                        contacts={data.contacts.clone()}
+                        stats={data.service_stats(date::CalendarDate::today())}
+                        on_select_service={on_select_service.clone()}
+                    />
+                    { lazy("portfolio", "portfolio", html! {
+                        <PortfolioSection
+                            portfolio={data.portfolio.clone()}
+                            services={data.services.clone()}
+                            selected_service={(*selected_service).clone()}
+                            on_select_service={on_select_service.clone()}
+                        />
+                    }) }
+                    { lazy("testimonials", "testimonials", html! {
+                        <TestimonialsSection
+                            testimonials={data.testimonials.clone()}
+                            contacts={data.contacts.clone()}
+                            services={data.services.clone()}
+                            selected_service={(*selected_service).clone()}
+                            on_select_service={on_select_service}
+                        />
+                    }) }
+                    { lazy("gift_certificates", "gift-certificates", html! {
+                        { for data.gift_certificates.clone().map(|certificates| html! {
+                            <GiftCertificatesSection
+                                certificates={certificates}
+                                services={data.services.clone()}
+                                info={data.info.clone()}
+                                contacts={data.contacts.clone()}
+                                legal={data.legal.clone()}
+                            />
+                        }) }
+                    }) }
+                    { lazy("faq", "faq", html! {
+                        { for data.faq.clone().map(|faq| html! {
+                            <FaqSection faq={faq} contacts={data.contacts.clone()} />
+                        }) }
+                    }) }
+                    <ContactSection
+                        contact={data.contact.clone()}
+                        info={data.info.clone()}
+                        contacts={data.contacts.clone()}
+                    />
+                </main>
+                <Footer
+                    footer={data.footer.clone()}
+                    contacts={data.contacts.clone()}
+                    legal={data.legal.clone()}
+                    on_cookie_settings={data
+                        .analytics
+                        .as_ref()
+                        .filter(|settings| settings.is_enabled())
+                        .map(|_| on_cookie_settings)}
+                />
+                <InstallBanner info={data.info.clone()} />
+                { for data.analytics.as_ref().filter(|_| *consent_pending).map(|settings| html! {
+                    <ConsentBanner text={settings.consent_text.clone()} on_choice={on_consent} />
+                }) }
+            </>
+        },
+        None if hero.is_some() && !*print_prices && page.is_none() => html! {
+            <>
+                { for (*hero).clone().map(|hero| html! { <HeroSection hero={hero} /> }) }
+                <div class="py-20 text-center text-amber-800 font-body">{ "Загрузка..." }</div>
             </>
         },
         None => html! {
@@ -44,18 +274,138 @@ fn app() -> Html {
     }
 }
 
-async fn load_content() -> Result<Content, String> {
-    let response = Request::get("/static/content.yaml")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch content: {:?}", e))?;
+/// Контент страницы; секции ниже первого экрана подставляются по мере прокрутки.
+#[derive(Default, PartialEq)]
+struct PageContent {
+    content: Option<Content>,
+    /// Секции, которые ещё не загружены: ключ секции → ссылка на её файл.
+    pending: HashMap<String, serde_json::Value>,
+    /// Секции, которые не удалось загрузить; они не показываются.
+    failed: HashSet<String>,
+}
+
+enum PageAction {
+    Loaded(Box<Content>, HashMap<String, serde_json::Value>),
+    Section(String, Option<content_loader::LazySection>),
+}
+
+impl Reducible for PageContent {
+    type Action = PageAction;
+
+    fn reduce(self: std::rc::Rc<Self>, action: PageAction) -> std::rc::Rc<Self> {
+        let mut next = Self {
+            content: self.content.clone(),
+            pending: self.pending.clone(),
+            failed: self.failed.clone(),
+        };
+        match action {
+            PageAction::Loaded(content, pending) => {
+                next.content = Some(*content);
+                next.pending = pending;
+            }
+            PageAction::Section(key, section) => {
+                next.pending.remove(&key);
+                match (section, next.content.as_mut()) {
+                    (Some(section), Some(content)) => section.apply(content),
+                    _ => {
+                        next.failed.insert(key);
+                    }
+                }
+            }
+        }
+        next.into()
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct LazySectionLoaderProps {
+    name: String,
+    /// Якорь секции: ссылка `#portfolio` ведёт сюда ещё до загрузки.
+    anchor: String,
+    value: serde_json::Value,
+    on_load: Callback<(String, Option<content_loader::LazySection>)>,
+}
+
+/// Место отложенной секции: загружает её файлы, когда место приближается к экрану.
+#[function_component(LazySectionLoader)]
+fn lazy_section_loader(props: &LazySectionLoaderProps) -> Html {
+    let node = use_node_ref();
 
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response text: {:?}", e))?;
+    {
+        let node = node.clone();
+        let name = props.name.clone();
+        let value = props.value.clone();
+        let on_load = props.on_load.clone();
+        use_effect_with((), move |_| {
+            let load = move || {
+                spawn_local(async move {
+                    let section = content_loader::load_lazy_section(&name, value)
+                        .await
+                        .map_err(|e| gloo_console::error!("Failed to load section:", e))
+                        .ok();
+                    on_load.emit((name, section));
+                })
+            };
+            // Без IntersectionObserver секция загружается сразу
+            let element = node.cast::<web_sys::Element>();
+            let observer = match element {
+                Some(element) if viewport::is_supported() => {
+                    viewport::observe_once(&element, "600px", load)
+                }
+                _ => {
+                    load();
+                    None
+                }
+            };
+            move || drop(observer)
+        });
+    }
 
-    serde_yaml::from_str(&text).map_err(|e| format!("Failed to parse YAML: {:?}", e))
+    html! {
+        <section
+            id={props.anchor.clone()}
+            ref={node}
+            aria-busy="true"
+            class="min-h-[50vh] py-20 px-4 flex items-center justify-center text-amber-800 font-body"
+        >
+            { "Загрузка..." }
+        </section>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct PromoBannerProps {
+    promo: data::PromoCode,
+}
+
+#[function_component(PromoBanner)]
+fn promo_banner(props: &PromoBannerProps) -> Html {
+    let visible = use_state(|| true);
+    if !*visible {
+        return html! {};
+    }
+
+    let onclose = {
+        let visible = visible.clone();
+        Callback::from(move |_| visible.set(false))
+    };
+
+    html! {
+        <div class="relative z-50 bg-gradient-to-r from-amber-600 to-orange-500 text-white px-12 py-3 text-center font-body shadow-lg" role="status">
+            <i class="fas fa-gift mr-2" aria-hidden="true"></i>
+            <span class="font-semibold">{ &props.promo.banner }</span>
+            <span class="block sm:inline sm:ml-2 text-sm text-white/90">
+                { format!("Код {} будет указан в заявке автоматически", props.promo.code) }
+            </span>
+            <button
+                onclick={onclose}
+                aria-label="Скрыть"
+                class="absolute right-3 top-1/2 -translate-y-1/2 text-white/80 hover:text-white"
+            >
+                <i class="fas fa-times" aria-hidden="true"></i>
+            </button>
+        </div>
+    }
 }
 
 #[derive(Properties, PartialEq)]
@@ -80,7 +430,7 @@ fn hero_section(props: &HeroSectionProps) -> Html {
                 />
                 <img
                     src={hero.background_image.clone()}
-                    alt=""
+                    alt={hero.background_alt.clone().unwrap_or_default()}
                     class="absolute inset-0 w-full h-full"
                     style="object-fit: cover; object-position: center; display: block;"
                     decoding="async"
@@ -94,20 +444,20 @@ fn hero_section(props: &HeroSectionProps) -> Html {
             // Контент поверх изображения в полупрозрачном блоке
             <div class="relative text-center px-4 py-20 max-w-4xl mx-auto" style="z-index: 10;">
                 <div class="backdrop-blur-md bg-white/50 rounded-3xl shadow-2xl p-8 md:p-12 border border-amber-200/50">
-                    <h1 class="text-5xl md:text-7xl font-heading font-extrabold mb-6 animate-fade-in text-amber-900">
+                    <h1 class="text-5xl md:text-7xl font-heading font-extrabold mb-6 motion-safe:animate-fade-in text-amber-900">
                         { &hero.title }
                     </h1>
                     <p class="text-xl md:text-2xl font-heading font-light mb-8 text-amber-800" style="font-weight: 300;">
                         { &hero.greeting }
                     </p>
-                    <p class="text-lg md:text-xl font-body font-light mb-8 text-amber-700 max-w-2xl mx-auto" style="font-weight: 300;">
-                        { &hero.description }
-                    </p>
+                    <div class="text-lg md:text-xl font-body font-light mb-8 text-amber-700 max-w-2xl mx-auto space-y-2" style="font-weight: 300;">
+                        { markdown::render(&hero.description) }
+                    </div>
                     <div class="flex flex-col sm:flex-row gap-4 justify-center mt-10">
-                        { for hero.buttons.iter().map(|btn| render_button(btn)) }
+                        { for hero.buttons.iter().map(render_button) }
                     </div>
                     <div class="mt-12">
-                        <i class={format!("fas {} text-5xl text-amber-600 opacity-70 animate-pulse", hero.icon)}></i>
+                        <i class={format!("fas {} text-5xl text-amber-600 opacity-70 motion-safe:animate-pulse", hero.icon)} aria-hidden="true"></i>
                     </div>
                 </div>
             </div>
@@ -117,14 +467,75 @@ fn hero_section(props: &HeroSectionProps) -> Html {
 
 fn render_button(button: &Button) -> Html {
     let class = if button.button_type == "primary" {
-        "bg-amber-600 text-white px-8 py-3 rounded-full font-body font-semibold hover:bg-amber-700 transition-all duration-300 shadow-lg"
+        "bg-amber-600 text-white px-8 py-3 rounded-full font-body font-semibold hover:bg-amber-700 transition-all motion-reduce:transition-none duration-300 shadow-lg"
     } else {
-        "bg-transparent border-2 border-amber-600 text-amber-700 px-8 py-3 rounded-full font-body font-semibold hover:bg-amber-600 hover:text-white transition-all duration-300"
+        "bg-transparent border-2 border-amber-600 text-amber-700 px-8 py-3 rounded-full font-body font-semibold hover:bg-amber-600 hover:text-white transition-all motion-reduce:transition-none duration-300"
     };
 
+    let onclick = analytics::on_click(analytics::Goal::Cta {
+        text: button.text.clone(),
+    });
+
     html! {
-        <a href={button.link.clone()} class={class}>
+        <BookingLink href={button.link.clone()} class={class} onclick={onclick}>
             { &button.text }
+        </BookingLink>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct BookingLinkProps {
+    href: AttrValue,
+    /// Текст заявки; по умолчанию — просто приветствие.
+    #[prop_or_default]
+    message: booking::BookingMessage,
+    #[prop_or_default]
+    class: Classes,
+    #[prop_or_default]
+    target: Option<AttrValue>,
+    #[prop_or_default]
+    aria_label: Option<AttrValue>,
+    #[prop_or_default]
+    onclick: Callback<MouseEvent>,
+    #[prop_or_default]
+    children: Html,
+}
+
+/// Ссылка на запись: код приглашения и рекламный источник попадают в заявку,
+/// а у кнопки онлайн-записи код ещё и написан под текстом.
+#[function_component(BookingLink)]
+fn booking_link(props: &BookingLinkProps) -> Html {
+    let href = props.message.link(&props.href);
+
+    let onclick = {
+        let href = props.href.clone();
+        let message = props.message.clone();
+        let onclick = props.onclick.clone();
+        Callback::from(move |e: MouseEvent| {
+            // Источник появляется после согласия на cookies, уже после отрисовки
+            let link = message.link(&href);
+            if let Some(anchor) = e
+                .current_target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+            {
+                let _ = anchor.set_attribute("href", &link);
+            }
+            onclick.emit(e);
+        })
+    };
+
+    html! {
+        <a
+            href={href}
+            class={props.class.clone()}
+            target={props.target.clone()}
+            aria-label={props.aria_label.clone()}
+            onclick={onclick}
+        >
+            { props.children.clone() }
+            { for booking::online_booking_code(&props.href).map(|code| html! {
+                <span class="block text-xs font-normal opacity-80">{ format!("Ваш код: {}", code) }</span>
+            }) }
         </a>
     }
 }
@@ -139,10 +550,10 @@ fn about_section(props: &AboutSectionProps) -> Html {
     let about = &props.about;
 
     html! {
-        <section class="py-20 px-4 bg-white">
+        <section id="about" class="py-20 px-4 bg-white" aria-labelledby="about-title">
             <div class="max-w-6xl mx-auto">
                 <div class="text-center mb-12">
-                    <h2 class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
+                    <h2 id="about-title" class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
                         { &about.title }
                     </h2>
                     <p class="text-lg text-amber-700 font-body">
@@ -152,14 +563,8 @@ fn about_section(props: &AboutSectionProps) -> Html {
 
                 <div class="grid md:grid-cols-2 gap-12 items-center mb-16">
                     <div class="order-2 md:order-1">
-                        <div class="prose prose-lg">
-                            { about.description.split("\n\n").map(|paragraph| {
-                                html! {
-                                    <p class="text-amber-800 font-body mb-4 leading-relaxed">
-                                        { paragraph.trim() }
-                                    </p>
-                                }
-                            }).collect::<Html>() }
+                        <div class="prose prose-lg text-amber-800 font-body leading-relaxed space-y-4">
+                            { markdown::render(&about.description) }
                         </div>
                     </div>
 
@@ -168,7 +573,7 @@ fn about_section(props: &AboutSectionProps) -> Html {
                             <div class="aspect-square rounded-2xl overflow-hidden shadow-2xl bg-gradient-to-br from-amber-100 to-orange-200">
                                 <img
                                     src={about.image.clone()}
-                                    alt={format!("{} - {}", &about.title, &about.subtitle)}
+                                    alt={about.image_alt.clone().unwrap_or_else(|| format!("{} - {}", &about.title, &about.subtitle))}
                                     class="w-full h-full object-cover"
                                 />
                             </div>
@@ -179,16 +584,16 @@ fn about_section(props: &AboutSectionProps) -> Html {
                 <div class="grid md:grid-cols-3 gap-8">
                     { for about.achievements.iter().map(|achievement| {
                         html! {
-                            <div class="text-center p-6 rounded-xl bg-amber-50 hover:bg-amber-100 transition-all duration-300">
+                            <div class="text-center p-6 rounded-xl bg-amber-50 hover:bg-amber-100 transition-all motion-reduce:transition-none duration-300">
                                 <div class="mb-4">
-                                    <i class={format!("fas {} text-4xl text-amber-600", achievement.icon)}></i>
+                                    <i class={format!("fas {} text-4xl text-amber-600", achievement.icon)} aria-hidden="true"></i>
                                 </div>
                                 <h3 class="text-xl font-heading font-bold text-amber-900 mb-2">
                                     { &achievement.title }
                                 </h3>
-                                <p class="text-amber-700 font-body">
-                                    { &achievement.description }
-                                </p>
+                                <div class="text-amber-700 font-body space-y-2">
+                                    { markdown::render(&achievement.description) }
+                                </div>
                             </div>
                         }
                     }) }
@@ -201,73 +606,150 @@ fn about_section(props: &AboutSectionProps) -> Html {
 #[derive(Properties, PartialEq)]
 struct ServicesSectionProps {
     services: data::ServicesSection,
+    info: data::SalonInfo,
     contacts: data::Contacts,
+    stats: HashMap<String, data::ServiceStats>,
+    on_select_service: Callback<Option<String>>,
 }
 
 #[function_component(ServicesSection)]
 fn services_section(props: &ServicesSectionProps) -> Html {
     let services = &props.services;
+    let calculator = use_state(|| false);
+    let selected = use_state(Vec::<String>::new);
+    // Время записи для файла календаря, значение поля datetime-local
+    let slot = use_state(String::new);
+
+    let on_toggle_calculator = {
+        let calculator = calculator.clone();
+        Callback::from(move |_| calculator.set(!*calculator))
+    };
+    let on_toggle_item = {
+        let selected = selected.clone();
+        Callback::from(move |id: String| {
+            let mut next = (*selected).clone();
+            match next.iter().position(|selected_id| *selected_id == id) {
+                Some(index) => {
+                    next.remove(index);
+                }
+                None => next.push(id),
+            }
+            selected.set(next);
+        })
+    };
+    let on_clear = {
+        let selected = selected.clone();
+        Callback::from(move |_| selected.set(Vec::new()))
+    };
 
     html! {
-        <section class="py-20 px-4 bg-gradient-to-br from-amber-50 via-yellow-50 to-orange-100">
+        <section id="services" class="py-20 px-4 bg-gradient-to-br from-amber-50 via-yellow-50 to-orange-100" aria-labelledby="services-title">
             <div class="max-w-7xl mx-auto">
                 <div class="text-center mb-16">
-                    <h2 class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
+                    <h2 id="services-title" class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
                         { &services.title }
                     </h2>
                     <p class="text-lg text-amber-700 font-body">
                         { &services.subtitle }
                     </p>
+                    <a
+                        href={format!("?{}={}", PRINT_PARAM, PRINT_PRICES)}
+                        target="_blank"
+                        class="inline-block mt-4 text-amber-700 hover:text-amber-900 font-body underline underline-offset-4"
+                    >
+                        <i class="fas fa-file-pdf mr-2" aria-hidden="true"></i>
+                        { "Прайс для печати" }
+                    </a>
+                    <button
+                        onclick={on_toggle_calculator}
+                        aria-pressed={calculator.to_string()}
+                        class="inline-block mt-4 ml-6 text-amber-700 hover:text-amber-900 font-body underline underline-offset-4"
+                    >
+                        <i class="fas fa-calculator mr-2" aria-hidden="true"></i>
+                        { if *calculator { "Скрыть калькулятор" } else { "Рассчитать стоимость" } }
+                    </button>
+                    { if *calculator && !services.combos.is_empty() {
+                        html! {
+                            <div class="flex flex-wrap justify-center gap-2 mt-4">
+                                { for services.combos.iter().map(|combo| html! {
+                                    <span class="bg-green-100 text-green-800 rounded-full px-4 py-1 text-sm font-body">
+                                        <i class="fas fa-gift mr-1" aria-hidden="true"></i>
+                                        { format!("{} {}", combo.name, combo.discount.label()) }
+                                    </span>
+                                }) }
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
                 </div>
 
                 <div class="grid md:grid-cols-3 gap-8">
                     { for services.categories.iter().map(|category| {
                         html! {
-                            <div class="bg-white rounded-2xl shadow-xl overflow-hidden hover:shadow-2xl transition-all duration-300 transform hover:-translate-y-2">
+                            <div class="bg-white rounded-2xl shadow-xl overflow-hidden hover:shadow-2xl transition-all motion-reduce:transition-none duration-300 transform motion-safe:hover:-translate-y-2">
                                 <div class="bg-gradient-to-r from-amber-500 to-orange-500 p-6 text-center">
                                     <div class="mb-3">
-                                        <i class={format!("fas {} text-5xl text-white", category.icon)}></i>
+                                        <i class={format!("fas {} text-5xl text-white", category.icon)} aria-hidden="true"></i>
                                     </div>
                                     <h3 class="text-2xl font-heading font-bold text-white mb-2">
                                         { &category.name }
                                     </h3>
-                                    <p class="text-white/90 font-body text-sm">
-                                        { &category.description }
-                                    </p>
+                                    <div class="text-white/90 font-body text-sm space-y-1">
+                                        { markdown::render(&category.description) }
+                                    </div>
                                 </div>
 
                                 <div class="p-6">
                                     <div class="space-y-4">
                                         { for category.items.iter().map(|item| {
+                                            let id = item.id.clone();
+                                            let is_selected = selected.contains(&item.id);
                                             html! {
                                                 <div class="border-b border-amber-100 pb-4 last:border-0">
                                                     <div class="flex justify-between items-start mb-2">
                                                         <h4 class="font-heading font-semibold text-amber-900 text-lg">
-                                                            { &item.name }
+                                                            { if *calculator {
+                                                                html! {
+                                                                    <label class="flex items-start gap-2 cursor-pointer">
+                                                                        <input
+                                                                            type="checkbox"
+                                                                            checked={is_selected}
+                                                                            onchange={on_toggle_item.reform(move |_| id.clone())}
+                                                                            class="mt-1.5 w-4 h-4 accent-amber-600"
+                                                                        />
+                                                                        { &item.name }
+                                                                    </label>
+                                                                }
+                                                            } else {
+                                                                html! { { &item.name } }
+                                                            }}
                                                         </h4>
                                                         <span class="font-heading font-bold text-amber-600 text-lg whitespace-nowrap ml-2">
-                                                            { &item.price }
+                                                            { item.price.as_str() }
                                                         </span>
                                                     </div>
-                                                    <p class="text-sm text-amber-700 font-body mb-1">
-                                                        { &item.description }
-                                                    </p>
+                                                    <div class="text-sm text-amber-700 font-body mb-1 space-y-1">
+                                                        { markdown::render(&item.description) }
+                                                    </div>
                                                     <div class="flex items-center text-xs text-amber-600 font-body">
-                                                        <i class="far fa-clock mr-1"></i>
-                                                        { &item.duration }
+                                                        <i class="far fa-clock mr-1" aria-hidden="true"></i>
+                                                        { item.duration.as_str() }
                                                     </div>
+                                                    { render_service_stats(item, props.stats.get(&item.id), &props.on_select_service) }
+                                                    <ServiceDetailsCard item={item.clone()} />
                                                 </div>
                                             }
                                         }) }
                                     </div>
 
                                     <div class="mt-6">
-                                        <a
+                                        <BookingLink
                                             href={props.contacts.link.clone()}
-                                            class="block w-full bg-amber-600 text-white text-center py-3 rounded-full font-body font-semibold hover:bg-amber-700 transition-all duration-300"
+                                            class="block w-full bg-amber-600 text-white text-center py-3 rounded-full font-body font-semibold hover:bg-amber-700 transition-all motion-reduce:transition-none duration-300"
                                         >
                                             { "Записаться" }
-                                        </a>
+                                        </BookingLink>
                                     </div>
                                 </div>
                             </div>
@@ -275,62 +757,316 @@ fn services_section(props: &ServicesSectionProps) -> Html {
                     }) }
                 </div>
             </div>
+
+            { if *calculator {
+                render_calculator_summary(
+                    services,
+                    &selected,
+                    &props.info,
+                    &props.contacts,
+                    &slot,
+                    on_clear,
+                )
+            } else {
+                html! {}
+            }}
         </section>
     }
 }
 
+/// Строка «N работ · ★4.9 (M отзывов)» со ссылками на работы и отзывы по услуге.
+fn render_service_stats(
+    item: &data::ServiceItem,
+    stats: Option<&data::ServiceStats>,
+    on_select_service: &Callback<Option<String>>,
+) -> Html {
+    let Some(stats) = stats.filter(|s| s.works > 0 || s.rating.is_some()) else {
+        return html! {};
+    };
+
+    let link = |anchor: &'static str, label: String| {
+        let on_select_service = on_select_service.clone();
+        let id = item.id.clone();
+        html! {
+            <a
+                href={anchor}
+                onclick={Callback::from(move |_| on_select_service.emit(Some(id.clone())))}
+                class="hover:text-amber-800 underline decoration-dotted underline-offset-2"
+            >
+                { label }
+            </a>
+        }
+    };
+
+    let works = (stats.works > 0).then(|| {
+        link(
+            "#portfolio",
+            format!(
+                "{} {}",
+                stats.works,
+                text::plural_ru(stats.works, "работа", "работы", "работ")
+            ),
+        )
+    });
+    let reviews = stats.rating.as_ref().map(|rating| {
+        link(
+            "#testimonials",
+            format!(
+                "★{} ({} {})",
+                rating::format_decimal(rating.average.value()),
+                rating.count,
+                text::plural_ru(rating.count, "отзыв", "отзыва", "отзывов")
+            ),
+        )
+    });
+    let separator = (works.is_some() && reviews.is_some()).then(|| html! { <span>{ "·" }</span> });
+
+    html! {
+        <div class="flex items-center gap-2 mt-2 text-xs text-amber-600 font-body">
+            { works }
+            { separator }
+            { reviews }
+        </div>
+    }
+}
+
+/// Плашка выбранной услуги с кнопкой сброса над галереей и отзывами.
+fn render_service_chip(
+    services: &data::ServicesSection,
+    selected_service: Option<&str>,
+    on_select_service: &Callback<Option<String>>,
+) -> Html {
+    let Some(service) = selected_service.and_then(|id| services.find(id)) else {
+        return html! {};
+    };
+
+    let onclick = {
+        let on_select_service = on_select_service.clone();
+        Callback::from(move |_| on_select_service.emit(None))
+    };
+
+    html! {
+        <div class="flex justify-center mb-8">
+            <span class="inline-flex items-center bg-amber-600 text-white rounded-full pl-5 pr-2 py-1 font-body text-sm shadow">
+                { format!("Услуга: {}", service.name) }
+                <button
+                    onclick={onclick}
+                    aria-label="Показать все"
+                    class="ml-2 w-7 h-7 rounded-full hover:bg-amber-700 transition-colors"
+                >
+                    <i class="fas fa-times" aria-hidden="true"></i>
+                </button>
+            </span>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct PriceSheetProps {
+    services: data::ServicesSection,
+    info: data::SalonInfo,
+    contacts: data::Contacts,
+}
+
+/// Компактный прайс для печати и сохранения в PDF (`?print=prices`).
+/// Разметку повторяет `cargo run -- prices sheet` для автономного HTML.
+#[function_component(PriceSheet)]
+fn price_sheet(props: &PriceSheetProps) -> Html {
+    let info = &props.info;
+    let contacts = &props.contacts;
+    let site_url = info.site_url.clone().or_else(url_query::page_url);
+    let qr = site_url.as_deref().and_then(|url| match qr::Qr::new(url) {
+        Ok(qr) => Some(qr),
+        Err(e) => {
+            gloo_console::error!("Failed to build QR code:", &e);
+            None
+        }
+    });
+
+    let onprint = Callback::from(|_| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.print();
+        }
+    });
+
+    html! {
+        <div class="min-h-screen bg-amber-50 print:bg-white py-8 print:py-0 px-4 print:px-0">
+            <div class="max-w-3xl mx-auto mb-6 flex justify-between items-center print:hidden">
+                <a href="./" class="text-amber-700 hover:text-amber-900 font-body">
+                    <i class="fas fa-arrow-left mr-2" aria-hidden="true"></i>
+                    { "На сайт" }
+                </a>
+                <button
+                    onclick={onprint}
+                    class="bg-amber-600 text-white px-6 py-2 rounded-full font-body font-semibold hover:bg-amber-700 transition-colors"
+                >
+                    <i class="fas fa-print mr-2" aria-hidden="true"></i>
+                    { "Печать / PDF" }
+                </button>
+            </div>
+
+            <article class="max-w-3xl mx-auto bg-white shadow-xl print:shadow-none rounded-2xl print:rounded-none p-10 print:p-0 font-body text-amber-900">
+                <header class="flex justify-between items-end border-b-2 border-amber-500 pb-4 mb-6">
+                    <div>
+                        <h1 class="text-4xl font-heading font-extrabold">{ &info.name }</h1>
+                        <p class="text-amber-700">{ &info.slogan }</p>
+                    </div>
+                    <div class="text-right">
+                        <p class="text-xl font-heading font-bold">{ &props.services.title }</p>
+                        <p class="text-sm text-amber-700">{ &info.city }</p>
+                    </div>
+                </header>
+
+                <div class="columns-1 md:columns-2 print:columns-2 gap-8">
+                    { for props.services.categories.iter().map(|category| html! {
+                        <section class="break-inside-avoid mb-6">
+                            <h2 class="text-lg font-heading font-bold text-amber-600 uppercase tracking-wide mb-2">
+                                <i class={format!("fas {} mr-2", category.icon)} aria-hidden="true"></i>
+                                { &category.name }
+                            </h2>
+                            <ul class="space-y-1">
+                                { for category.items.iter().map(|item| html! {
+                                    <li class="flex items-baseline gap-2 text-sm">
+                                        <span>{ &item.name }</span>
+                                        <span class="text-xs text-amber-600 whitespace-nowrap">{ item.duration.as_str() }</span>
+                                        <span class="flex-1 border-b border-dotted border-amber-300"></span>
+                                        <span class="font-semibold whitespace-nowrap">{ item.price.as_str() }</span>
+                                    </li>
+                                }) }
+                            </ul>
+                        </section>
+                    }) }
+                </div>
+
+                <footer class="flex justify-between items-center gap-6 border-t-2 border-amber-500 pt-4 mt-2 text-sm">
+                    <div class="space-y-1">
+                        <p><i class="fas fa-phone mr-2 text-amber-600" aria-hidden="true"></i>{ &contacts.phone }</p>
+                        <p><i class="fas fa-map-marker-alt mr-2 text-amber-600" aria-hidden="true"></i>{ &contacts.address }</p>
+                        <p><i class="far fa-clock mr-2 text-amber-600" aria-hidden="true"></i>{ &contacts.working_hours }</p>
+                        { for site_url.iter().map(|url| html! {
+                            <p><i class="fas fa-globe mr-2 text-amber-600" aria-hidden="true"></i>{ url }</p>
+                        }) }
+                    </div>
+                    { for qr.map(|qr| html! {
+                        <svg
+                            class="w-28 h-28 shrink-0"
+                            viewBox={format!("0 0 {0} {0}", qr.size)}
+                            shape-rendering="crispEdges"
+                        >
+                            <rect width="100%" height="100%" fill="white" />
+                            <path d={qr.path} fill="#78350f" />
+                        </svg>
+                    }) }
+                </footer>
+            </article>
+        </div>
+    }
+}
+
 #[derive(Properties, PartialEq)]
 struct PortfolioSectionProps {
     portfolio: data::PortfolioSection,
+    services: data::ServicesSection,
+    selected_service: Option<String>,
+    on_select_service: Callback<Option<String>>,
 }
 
 #[function_component(PortfolioSection)]
 fn portfolio_section(props: &PortfolioSectionProps) -> Html {
     let portfolio = &props.portfolio;
-    let selected_filter = use_state(|| "Все".to_string());
+    let default_mode = portfolio.filter_mode;
+    let filter = use_state(|| PortfolioFilter::from_location(default_mode));
 
-    let filtered_items = if *selected_filter == "Все" {
-        portfolio.items.clone()
-    } else {
-        portfolio
-            .items
-            .iter()
-            .filter(|item| item.category == *selected_filter)
-            .cloned()
-            .collect::<Vec<_>>()
+    {
+        let filter = filter.clone();
+        use_effect_with((*filter).clone(), move |current| {
+            current.write_to_location(default_mode);
+            || ()
+        });
+    }
+
+    // Ключ карточки — позиция работы в полном списке, чтобы состояние карусели
+    // оставалось у своей работы при фильтрации
+    let filtered_items = portfolio
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| filter.matches(item))
+        .filter(|(_, item)| {
+            props
+                .selected_service
+                .as_ref()
+                .is_none_or(|id| item.service_ids.contains(id))
+        })
+        .map(|(position, item)| (position, item.clone()))
+        .collect::<Vec<_>>();
+
+    let oninput = {
+        let filter = filter.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                filter.set(filter.with_query(input.value()));
+            }
+        })
     };
 
     html! {
-        <section class="py-20 px-4 bg-white">
+        <section id="portfolio" class="py-20 px-4 bg-white" aria-labelledby="portfolio-title">
             <div class="max-w-7xl mx-auto">
                 <div class="text-center mb-12">
-                    <h2 class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
+                    <h2 id="portfolio-title" class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
                         { &portfolio.title }
                     </h2>
                     <p class="text-xl text-amber-700 font-body mb-2">
                         { &portfolio.subtitle }
                     </p>
-                    <p class="text-md text-amber-600 font-body">
-                        { &portfolio.description }
-                    </p>
+                    <div class="text-md text-amber-600 font-body space-y-2">
+                        { markdown::render(&portfolio.description) }
+                    </div>
+                </div>
+
+                // Поиск
+                <div class="max-w-md mx-auto mb-6 relative">
+                    <i class="fas fa-search absolute left-4 top-1/2 -translate-y-1/2 text-amber-400" aria-hidden="true"></i>
+                    <input
+                        type="search"
+                        value={filter.query.clone()}
+                        oninput={oninput}
+                        placeholder="Поиск по работам"
+                        aria-label="Поиск по работам"
+                        class="w-full pl-11 pr-4 py-2 rounded-full border-2 border-amber-200 focus:border-amber-500 focus:outline-none focus:ring-4 focus:ring-amber-200 font-body text-amber-900"
+                    />
                 </div>
 
                 // Фильтры
-                <div class="flex flex-wrap justify-center gap-3 mb-12">
-                    { for portfolio.filters.iter().map(|filter| {
-                        let filter_clone = filter.clone();
-                        let selected_filter_clone = selected_filter.clone();
-                        let is_active = *selected_filter == *filter;
+                <div class="flex flex-wrap justify-center gap-3 mb-4">
+                    { for portfolio.filters.iter().map(|tag| {
+                        let is_reset = tag == ALL_FILTER;
+                        let is_active = if is_reset {
+                            filter.tags.is_empty()
+                        } else {
+                            filter.is_selected(tag)
+                        };
 
-                        let onclick = Callback::from(move |_| {
-                            selected_filter_clone.set(filter_clone.clone());
-                        });
+                        let onclick = {
+                            let filter = filter.clone();
+                            let tag = tag.clone();
+                            Callback::from(move |_| {
+                                analytics::reach_goal(&analytics::Goal::PortfolioFilter { tag: tag.clone() });
+                                if is_reset {
+                                    filter.set(filter.clear_tags());
+                                } else {
+                                    filter.set(filter.toggle_tag(&tag));
+                                }
+                            })
+                        };
 
                         html! {
                             <button
                                 onclick={onclick}
+                                aria-pressed={is_active.to_string()}
                                 class={format!(
-                                    "px-6 py-2 rounded-full font-body font-semibold transition-all duration-300 {}",
+                                    "px-6 py-2 rounded-full font-body font-semibold transition-all motion-reduce:transition-none duration-300 {}",
                                     if is_active {
                                         "bg-amber-600 text-white shadow-lg"
                                     } else {
@@ -338,38 +1074,52 @@ fn portfolio_section(props: &PortfolioSectionProps) -> Html {
                                     }
                                 )}
                             >
-                                { filter }
+                                { tag }
                             </button>
                         }
                     }) }
                 </div>
 
+                // Режим сочетания тегов
+                { if filter.tags.len() > 1 {
+                    html! {
+                        <div class="flex justify-center items-center gap-2 mb-12 text-sm font-body text-amber-700">
+                            <span>{ "Показывать работы:" }</span>
+                            { for [(data::FilterMode::Any, "с любым из тегов"), (data::FilterMode::All, "со всеми тегами")].into_iter().map(|(mode, label)| {
+                                let filter_clone = filter.clone();
+                                let onclick = Callback::from(move |_| {
+                                    filter_clone.set(filter_clone.with_mode(mode));
+                                });
+                                html! {
+                                    <button
+                                        onclick={onclick}
+                                        aria-pressed={(filter.mode == mode).to_string()}
+                                        class={format!(
+                                            "px-3 py-1 rounded-full transition-all motion-reduce:transition-none duration-300 {}",
+                                            if filter.mode == mode {
+                                                "bg-amber-600 text-white"
+                                            } else {
+                                                "bg-amber-50 text-amber-700 hover:bg-amber-100"
+                                            }
+                                        )}
+                                    >
+                                        { label }
+                                    </button>
+                                }
+                            }) }
+                        </div>
+                    }
+                } else {
+                    html! { <div class="mb-12"></div> }
+                }}
+
+                { render_service_chip(&props.services, props.selected_service.as_deref(), &props.on_select_service) }
+
                 // Галерея
                 <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 gap-6">
-                    { for filtered_items.iter().map(|item| {
+                    { for filtered_items.iter().map(|(position, item)| {
                         html! {
-                            <div class="group relative overflow-hidden rounded-2xl shadow-lg hover:shadow-2xl transition-all duration-300 transform hover:-translate-y-2 bg-gradient-to-br from-amber-100 to-orange-200">
-                                <div class="aspect-square overflow-hidden">
-                                    <img
-                                        src={item.image.clone()}
-                                        alt={item.title.clone()}
-                                        class="w-full h-full object-cover group-hover:scale-110 transition-transform duration-500"
-                                    />
-                                </div>
-
-                                // Оверлей с информацией
-                                <div class="absolute inset-0 bg-gradient-to-t from-amber-900/90 via-amber-900/50 to-transparent opacity-0 group-hover:opacity-100 transition-opacity duration-300 flex flex-col justify-end p-6">
-                                    <span class="text-amber-300 text-sm font-body mb-1">
-                                        { &item.category }
-                                    </span>
-                                    <h3 class="text-white text-xl font-heading font-bold mb-2">
-                                        { &item.title }
-                                    </h3>
-                                    <p class="text-white/90 text-sm font-body">
-                                        { &item.description }
-                                    </p>
-                                </div>
-                            </div>
+                            <PortfolioCard key={*position} item={item.clone()} />
                         }
                     }) }
                 </div>
@@ -390,21 +1140,262 @@ fn portfolio_section(props: &PortfolioSectionProps) -> Html {
     }
 }
 
+#[derive(Properties, PartialEq)]
+struct PortfolioCardProps {
+    item: data::PortfolioItem,
+}
+
+#[function_component(PortfolioCard)]
+fn portfolio_card(props: &PortfolioCardProps) -> Html {
+    let item = &props.item;
+    let slides = item.slides();
+    let kind = item.media_kind();
+    let current = use_state(|| 0usize);
+    let touch_start = use_mut_ref(|| None::<i32>);
+    let video_ref = use_node_ref();
+
+    let count = slides.len();
+    let index = (*current).min(count.saturating_sub(1));
+    let alt = item.alt.as_deref().unwrap_or(&item.title);
+    let alt = if count > 1 {
+        format!("{} (фото {} из {})", alt, index + 1, count)
+    } else {
+        alt.to_string()
+    };
+
+    let show = {
+        let current = current.clone();
+        let title = item.title.clone();
+        move |delta: isize| {
+            if count > 1 {
+                analytics::reach_goal(&analytics::Goal::PortfolioSlide {
+                    title: title.clone(),
+                });
+                let next = (index as isize + delta).rem_euclid(count as isize) as usize;
+                current.set(next);
+            }
+        }
+    };
+
+    let onprev = {
+        let show = show.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.stop_propagation();
+            show(-1);
+        })
+    };
+    let onnext = {
+        let show = show.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.stop_propagation();
+            show(1);
+        })
+    };
+
+    let ontouchstart = {
+        let touch_start = touch_start.clone();
+        Callback::from(move |e: TouchEvent| {
+            *touch_start.borrow_mut() = e.touches().get(0).map(|t| t.client_x());
+        })
+    };
+    let ontouchend = {
+        let touch_start = touch_start.clone();
+        Callback::from(move |e: TouchEvent| {
+            let start = touch_start.borrow_mut().take();
+            let end = e.changed_touches().get(0).map(|t| t.client_x());
+            if let (Some(start), Some(end)) = (start, end) {
+                // Свайп короче 40px считаем случайным касанием
+                if end - start > 40 {
+                    show(-1);
+                } else if start - end > 40 {
+                    show(1);
+                }
+            }
+        })
+    };
+
+    // На телефоне наведения нет: видео играет, пока карточка на экране
+    {
+        let video_ref = video_ref.clone();
+        let is_video = matches!(slides.get(index), Some(data::Media::Video { .. }));
+        use_effect_with((index, is_video), move |&(_, is_video)| {
+            let observer = video_ref
+                .cast::<web_sys::HtmlVideoElement>()
+                .filter(|_| is_video && viewport::is_touch_only())
+                .filter(|_| !viewport::prefers_reduced_motion())
+                .and_then(|video| {
+                    let element = video.clone().into();
+                    viewport::observe(&element, "-25% 0px", move |visible| {
+                        if visible {
+                            play_muted(&video);
+                        } else {
+                            let _ = video.pause();
+                        }
+                    })
+                });
+            move || drop(observer)
+        });
+    }
+
+    let onmouseenter = {
+        let video_ref = video_ref.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(video) = video_ref
+                .cast::<web_sys::HtmlVideoElement>()
+                .filter(|_| !viewport::prefers_reduced_motion())
+            {
+                play_muted(&video);
+            }
+        })
+    };
+    let onmouseleave = {
+        let video_ref = video_ref.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(video) = video_ref.cast::<web_sys::HtmlVideoElement>() {
+                let _ = video.pause();
+            }
+        })
+    };
+
+    let slide = match slides.get(index) {
+        Some(data::Media::Video { sources, poster }) => html! {
+            <video
+                ref={video_ref.clone()}
+                poster={poster.clone()}
+                aria-label={alt.clone()}
+                muted={true}
+                loop={true}
+                playsinline={true}
+                preload="none"
+                class="w-full h-full object-cover"
+            >
+                { for sources.iter().map(|src| html! {
+                    <source src={src.clone()} type={data::video_mime(src)} />
+                }) }
+            </video>
+        },
+        Some(data::Media::Image { src }) => html! {
+            <img
+                src={src.clone()}
+                alt={alt}
+                loading="lazy"
+                class="w-full h-full object-cover motion-safe:group-hover:scale-110 transition-transform motion-reduce:transition-none duration-500"
+            />
+        },
+        Some(data::Media::Carousel { .. }) | None => html! {},
+    };
+
+    html! {
+        <div
+            class="group relative overflow-hidden rounded-2xl shadow-lg hover:shadow-2xl transition-all motion-reduce:transition-none duration-300 transform motion-safe:hover:-translate-y-2 bg-gradient-to-br from-amber-100 to-orange-200"
+            onmouseenter={onmouseenter}
+            onmouseleave={onmouseleave}
+            ontouchstart={ontouchstart}
+            ontouchend={ontouchend}
+        >
+            <div class="aspect-square overflow-hidden">
+                { slide }
+            </div>
+
+            // Тип медиа
+            <span class="absolute top-3 left-3 z-10 bg-white/80 backdrop-blur-sm text-amber-800 text-xs font-body font-semibold px-3 py-1 rounded-full shadow inline-flex items-center">
+                <i class={format!("fas {} mr-1", kind.icon())} aria-hidden="true"></i>
+                { kind.label() }
+            </span>
+
+            // Оверлей с информацией
+            <div class="absolute inset-0 bg-gradient-to-t from-amber-900/90 via-amber-900/50 to-transparent opacity-0 group-hover:opacity-100 transition-opacity duration-300 flex flex-col justify-end p-6 pointer-events-none">
+                <span class="text-amber-300 text-sm font-body mb-1">
+                    { item.tags().join(" · ") }
+                </span>
+                <h3 class="text-white text-xl font-heading font-bold mb-2">
+                    { &item.title }
+                </h3>
+                <div class="text-white/90 text-sm font-body space-y-1">
+                    { markdown::render(&item.description) }
+                </div>
+            </div>
+
+            // Навигация по карусели
+            { if count > 1 {
+                html! {
+                    <>
+                        <button
+                            onclick={onprev}
+                            aria-label="Предыдущее фото"
+                            class="absolute left-2 top-1/2 -translate-y-1/2 z-10 w-9 h-9 rounded-full bg-white/80 text-amber-800 shadow opacity-0 group-hover:opacity-100 focus-visible:opacity-100 focus:outline-none focus-visible:ring-4 focus-visible:ring-amber-300 transition-opacity motion-reduce:transition-none duration-300"
+                        >
+                            <i class="fas fa-chevron-left" aria-hidden="true"></i>
+                        </button>
+                        <button
+                            onclick={onnext}
+                            aria-label="Следующее фото"
+                            class="absolute right-2 top-1/2 -translate-y-1/2 z-10 w-9 h-9 rounded-full bg-white/80 text-amber-800 shadow opacity-0 group-hover:opacity-100 focus-visible:opacity-100 focus:outline-none focus-visible:ring-4 focus-visible:ring-amber-300 transition-opacity motion-reduce:transition-none duration-300"
+                        >
+                            <i class="fas fa-chevron-right" aria-hidden="true"></i>
+                        </button>
+                        <div class="absolute top-4 right-3 z-10 flex gap-1">
+                            { for (0..count).map(|i| html! {
+                                <span class={format!(
+                                    "w-2 h-2 rounded-full {}",
+                                    if i == index { "bg-white" } else { "bg-white/50" }
+                                )}></span>
+                            }) }
+                        </div>
+                    </>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+/// Запускает видео без звука. Атрибут `muted` из разметки не выставляет
+/// свойство, а без него браузеры отклоняют автозапуск.
+fn play_muted(video: &web_sys::HtmlVideoElement) {
+    video.set_muted(true);
+    match video.play() {
+        Ok(promise) => spawn_local(async move {
+            if let Err(e) = wasm_bindgen_futures::JsFuture::from(promise).await {
+                gloo_console::warn!("Video playback rejected:", e);
+            }
+        }),
+        Err(e) => gloo_console::warn!("Video playback failed:", e),
+    }
+}
+
 #[derive(Properties, PartialEq)]
 struct TestimonialsSectionProps {
     testimonials: data::TestimonialsSection,
     contacts: data::Contacts,
+    services: data::ServicesSection,
+    selected_service: Option<String>,
+    on_select_service: Callback<Option<String>>,
 }
 
 #[function_component(TestimonialsSection)]
 fn testimonials_section(props: &TestimonialsSectionProps) -> Html {
     let testimonials = &props.testimonials;
+    let sort = use_state(|| testimonials.sort);
+    let today = date::CalendarDate::today();
+    let mut items = testimonials
+        .visible_items(today)
+        .filter(|t| {
+            props
+                .selected_service
+                .as_ref()
+                .is_none_or(|id| t.service_ids.contains(id))
+        })
+        .collect::<Vec<_>>();
+    sort.apply(&mut items);
+    let items = items.into_iter().cloned().collect::<Vec<_>>();
 
     html! {
-        <section class="py-20 px-4 bg-gradient-to-br from-amber-50 via-yellow-50 to-orange-100">
+        <section id="testimonials" class="py-20 px-4 bg-gradient-to-br from-amber-50 via-yellow-50 to-orange-100" aria-labelledby="testimonials-title">
             <div class="max-w-7xl mx-auto">
                 <div class="text-center mb-16">
-                    <h2 class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
+                    <h2 id="testimonials-title" class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
                         { &testimonials.title }
                     </h2>
                     <p class="text-lg text-amber-700 font-body">
@@ -412,63 +1403,58 @@ fn testimonials_section(props: &TestimonialsSectionProps) -> Html {
                     </p>
                 </div>
 
-                <div class="grid md:grid-cols-2 lg:grid-cols-3 gap-8">
-                    { for testimonials.items.iter().map(|testimonial| {
-                        html! {
-                            <div class="bg-white rounded-2xl shadow-lg hover:shadow-2xl transition-all duration-300 p-6 transform hover:-translate-y-2">
-                                // Аватар и имя
-                                <div class="flex items-center mb-4">
-                                    <div class="w-14 h-14 rounded-full bg-gradient-to-br from-amber-400 to-orange-500 flex items-center justify-center text-white font-heading font-bold text-lg mr-4">
-                                        { &testimonial.avatar }
-                                    </div>
-                                    <div>
-                                        <h3 class="font-heading font-bold text-amber-900 text-lg">
-                                            { &testimonial.name }
-                                        </h3>
-                                        <p class="text-sm text-amber-600 font-body">
-                                            { &testimonial.service }
-                                        </p>
-                                    </div>
-                                </div>
+                { for testimonials.rating_summary(today).map(|summary| render_rating_summary(&summary)) }
 
-                                // Рейтинг
-                                <div class="flex mb-3">
-                                    { for (0..testimonial.rating).map(|_| {
-                                        html! {
-                                            <i class="fas fa-star text-amber-500"></i>
-                                        }
-                                    }) }
-                                </div>
-
-                                // Текст отзыва
-                                <div class="relative mb-4">
-                                    <i class="fas fa-quote-left text-3xl text-amber-200 absolute -top-2 -left-1"></i>
-                                    <p class="text-amber-800 font-body leading-relaxed pl-8 pr-4">
-                                        { &testimonial.text }
-                                    </p>
-                                    <i class="fas fa-quote-right text-3xl text-amber-200 absolute -bottom-2 right-0"></i>
-                                </div>
+                { render_service_chip(&props.services, props.selected_service.as_deref(), &props.on_select_service) }
 
-                                // Дата
-                                <div class="text-sm text-amber-600 font-body text-right">
-                                    { &testimonial.date }
-                                </div>
-                            </div>
+                // Сортировка
+                <div class="flex flex-wrap justify-center gap-2 mb-8 text-sm font-body">
+                    { for data::TestimonialSort::ALL.into_iter().map(|option| {
+                        let sort_clone = sort.clone();
+                        let is_active = *sort == option;
+                        html! {
+                            <button
+                                onclick={Callback::from(move |_| sort_clone.set(option))}
+                                aria-pressed={is_active.to_string()}
+                                class={format!(
+                                    "px-4 py-1 rounded-full transition-all motion-reduce:transition-none duration-300 {}",
+                                    if is_active {
+                                        "bg-amber-600 text-white shadow"
+                                    } else {
+                                        "bg-white text-amber-700 hover:bg-amber-100"
+                                    }
+                                )}
+                            >
+                                { option.label() }
+                            </button>
                         }
                     }) }
                 </div>
 
+                { match testimonials.layout {
+                    data::TestimonialsLayout::Grid => html! {
+                        <div class="grid md:grid-cols-2 lg:grid-cols-3 gap-8">
+                            { for items.iter().map(|testimonial| html! {
+                                <TestimonialCard key={testimonial.key()} testimonial={testimonial.clone()} today={today} />
+                            }) }
+                        </div>
+                    },
+                    data::TestimonialsLayout::Carousel => html! {
+                        <TestimonialCarousel items={items.clone()} autoplay_seconds={testimonials.autoplay_seconds} today={today} />
+                    },
+                }}
+
                 // Ссылка на больше отзывов
                 <div class="text-center mt-12">
                     <p class="text-amber-700 font-body mb-4">
                         { "Больше отзывов в моих социальных сетях" }
                     </p>
                     <div class="flex justify-center gap-4">
-                        <a href={props.contacts.instagram.clone()} target="_blank" class="text-amber-600 hover:text-amber-700 transition-colors duration-300">
-                            <i class="fab fa-instagram text-3xl"></i>
+                        <a href={props.contacts.instagram.clone()} target="_blank" aria-label="Instagram" class="text-amber-600 hover:text-amber-700 transition-colors duration-300">
+                            <i class="fab fa-instagram text-3xl" aria-hidden="true"></i>
                         </a>
-                        <a href={props.contacts.telegram.clone()} target="_blank" class="text-amber-600 hover:text-amber-700 transition-colors duration-300">
-                            <i class="fab fa-telegram text-3xl"></i>
+                        <a href={props.contacts.telegram.clone()} target="_blank" aria-label="Telegram" class="text-amber-600 hover:text-amber-700 transition-colors duration-300">
+                            <i class="fab fa-telegram text-3xl" aria-hidden="true"></i>
                         </a>
                     </div>
                 </div>
@@ -477,9 +1463,77 @@ fn testimonials_section(props: &TestimonialsSectionProps) -> Html {
     }
 }
 
+fn render_stars(rating: rating::Rating, class: &str) -> Html {
+    html! {
+        <div class={classes!("flex", "gap-0.5", class.to_string())} role="img" aria-label={rating.label()}>
+            { for rating.stars().into_iter().map(|star| html! {
+                <i class={star.icon()} aria-hidden="true"></i>
+            }) }
+        </div>
+    }
+}
+
+/// Средняя оценка, число отзывов и гистограмма распределения по звёздам.
+fn render_rating_summary(summary: &rating::RatingSummary) -> Html {
+    let max = summary
+        .distribution
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    html! {
+        <div class="max-w-2xl mx-auto mb-12 bg-white rounded-2xl shadow-lg p-6 flex flex-col sm:flex-row items-center gap-8">
+            <div class="text-center flex-shrink-0">
+                <div class="text-5xl font-heading font-extrabold text-amber-900">
+                    { rating::format_decimal(summary.average.value()) }
+                </div>
+                { render_stars(summary.average, "text-amber-500 text-lg justify-center my-2") }
+                <div class="text-sm text-amber-600 font-body">
+                    { format!("{} {}", summary.count, text::plural_ru(summary.count, "отзыв", "отзыва", "отзывов")) }
+                </div>
+            </div>
+
+            <div class="w-full space-y-1">
+                { for (1..=5).rev().map(|stars| {
+                    let count = summary.distribution[stars - 1];
+                    let width = count * 100 / max;
+                    html! {
+                        <div class="flex items-center gap-3 text-sm font-body text-amber-700">
+                            <span class="w-8 text-right whitespace-nowrap">
+                                { stars }
+                                <i class="fas fa-star text-amber-500 text-xs ml-1" aria-hidden="true"></i>
+                            </span>
+                            <div class="flex-1 h-2 rounded-full bg-amber-100 overflow-hidden">
+                                <div class="h-full rounded-full bg-amber-500" style={format!("width: {}%;", width)}></div>
+                            </div>
+                            <span class="w-6 text-right">{ count }</span>
+                        </div>
+                    }
+                }) }
+            </div>
+        </div>
+    }
+}
+
+/// Обработчик ввода, сохраняющий значение текстового поля в состояние.
+fn on_text_input(state: &UseStateHandle<String>) -> Callback<InputEvent> {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+        if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+            state.set(input.value());
+        } else if let Some(area) = e.target_dyn_into::<web_sys::HtmlTextAreaElement>() {
+            state.set(area.value());
+        }
+    })
+}
+
 #[derive(Properties, PartialEq)]
 struct ContactSectionProps {
     contact: data::ContactSection,
+    info: data::SalonInfo,
+    contacts: data::Contacts,
 }
 
 #[function_component(ContactSection)]
@@ -487,18 +1541,18 @@ fn contact_section(props: &ContactSectionProps) -> Html {
     let contact = &props.contact;
 
     html! {
-        <section class="py-20 px-4 bg-white">
+        <section id="contact" class="py-20 px-4 bg-white" aria-labelledby="contact-title">
             <div class="max-w-7xl mx-auto">
                 <div class="text-center mb-16">
-                    <h2 class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
+                    <h2 id="contact-title" class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
                         { &contact.title }
                     </h2>
                     <p class="text-xl text-amber-700 font-body mb-2">
                         { &contact.subtitle }
                     </p>
-                    <p class="text-md text-amber-600 font-body">
-                        { &contact.description }
-                    </p>
+                    <div class="text-md text-amber-600 font-body space-y-2">
+                        { markdown::render(&contact.description) }
+                    </div>
                 </div>
 
                 <div class="grid md:grid-cols-2 gap-12">
@@ -512,11 +1566,11 @@ fn contact_section(props: &ContactSectionProps) -> Html {
                             // Телефон
                             <div class="flex items-start mb-4">
                                 <div class="w-12 h-12 bg-amber-600 rounded-full flex items-center justify-center text-white mr-4 flex-shrink-0">
-                                    <i class="fas fa-phone-alt"></i>
+                                    <i class="fas fa-phone-alt" aria-hidden="true"></i>
                                 </div>
                                 <div>
                                     <p class="text-sm text-amber-600 font-body mb-1">{ "Телефон" }</p>
-                                    <a href={contact.phone_link.clone()} class="text-lg font-heading font-semibold text-amber-900 hover:text-amber-700 transition-colors">
+                                    <a href={contact.phone_link.clone()} onclick={analytics::on_click(analytics::Goal::Phone)} class="text-lg font-heading font-semibold text-amber-900 hover:text-amber-700 transition-colors">
                                         { &contact.phone }
                                     </a>
                                 </div>
@@ -525,11 +1579,11 @@ fn contact_section(props: &ContactSectionProps) -> Html {
                             // Email
                             <div class="flex items-start mb-4">
                                 <div class="w-12 h-12 bg-amber-600 rounded-full flex items-center justify-center text-white mr-4 flex-shrink-0">
-                                    <i class="fas fa-envelope"></i>
+                                    <i class="fas fa-envelope" aria-hidden="true"></i>
                                 </div>
                                 <div>
                                     <p class="text-sm text-amber-600 font-body mb-1">{ "Email" }</p>
-                                    <a href={format!("mailto:{}", contact.email)} class="text-lg font-heading font-semibold text-amber-900 hover:text-amber-700 transition-colors">
+                                    <a href={format!("mailto:{}", contact.email)} onclick={analytics::on_click(analytics::Goal::Email)} class="text-lg font-heading font-semibold text-amber-900 hover:text-amber-700 transition-colors">
                                         { &contact.email }
                                     </a>
                                 </div>
@@ -538,20 +1592,33 @@ fn contact_section(props: &ContactSectionProps) -> Html {
                             // Адрес
                             <div class="flex items-start mb-4">
                                 <div class="w-12 h-12 bg-amber-600 rounded-full flex items-center justify-center text-white mr-4 flex-shrink-0">
-                                    <i class="fas fa-map-marker-alt"></i>
+                                    <i class="fas fa-map-marker-alt" aria-hidden="true"></i>
                                 </div>
                                 <div>
                                     <p class="text-sm text-amber-600 font-body mb-1">{ "Адрес" }</p>
                                     <p class="text-lg font-body text-amber-900">
-                                        { &contact.address }
+                                        { &contact.location.address }
                                     </p>
+                                    { for contact.location.entrance.as_ref().map(|entrance| html! {
+                                        <div class="text-sm font-body text-amber-700 mt-1 space-y-1">
+                                            { markdown::render(entrance) }
+                                        </div>
+                                    }) }
+                                    { for contact.location.entrance_photo.as_ref().map(|photo| html! {
+                                        <img
+                                            src={photo.clone()}
+                                            alt={contact.location.entrance_photo_alt.clone().unwrap_or_else(|| "Вход в студию".to_string())}
+                                            loading="lazy"
+                                            class="mt-3 w-full max-w-xs rounded-xl shadow"
+                                        />
+                                    }) }
                                 </div>
                             </div>
 
                             // Часы работы
                             <div class="flex items-start">
                                 <div class="w-12 h-12 bg-amber-600 rounded-full flex items-center justify-center text-white mr-4 flex-shrink-0">
-                                    <i class="fas fa-clock"></i>
+                                    <i class="fas fa-clock" aria-hidden="true"></i>
                                 </div>
                                 <div>
                                     <p class="text-sm text-amber-600 font-body mb-1">{ "Часы работы" }</p>
@@ -560,6 +1627,15 @@ fn contact_section(props: &ContactSectionProps) -> Html {
                                     </p>
                                 </div>
                             </div>
+
+                            <a
+                                href={url_query::data_url("text/vcard", &vcard::salon_card(&props.info, &props.contacts))}
+                                download={format!("{}.vcf", text::slug(&props.info.name))}
+                                class="mt-6 inline-flex items-center bg-white text-amber-700 px-6 py-2 rounded-full font-body font-semibold shadow hover:shadow-lg transition-all motion-reduce:transition-none duration-300"
+                            >
+                                <i class="fas fa-address-card mr-2" aria-hidden="true"></i>
+                                { "Сохранить контакт" }
+                            </a>
                         </div>
 
                         // Социальные сети
@@ -569,15 +1645,27 @@ fn contact_section(props: &ContactSectionProps) -> Html {
                             </h3>
                             <div class="grid grid-cols-2 gap-4">
                                 { for contact.social.iter().map(|social| {
-                                    html! {
-                                        <a
-                                            href={social.link.clone()}
-                                            target="_blank"
-                                            class={format!("flex items-center justify-center bg-white rounded-xl p-4 shadow hover:shadow-lg transition-all duration-300 transform hover:-translate-y-1 text-amber-600 {}", social.color)}
-                                        >
-                                            <i class={format!("fab {} text-3xl mr-3", social.icon)}></i>
+                                    let class = format!("flex items-center justify-center bg-white rounded-xl p-4 shadow hover:shadow-lg transition-all motion-reduce:transition-none duration-300 transform motion-safe:hover:-translate-y-1 text-amber-600 {}", social.color);
+                                    let onclick = analytics::on_click(analytics::Goal::Social { network: social.name.clone() });
+                                    let content = html! {
+                                        <>
+                                            <i class={format!("fab {} text-3xl mr-3", social.icon)} aria-hidden="true"></i>
                                             <span class="font-body font-semibold">{ &social.name }</span>
-                                        </a>
+                                        </>
+                                    };
+                                    // В мессенджер заявка уходит с кодом приглашения
+                                    if booking::is_messenger(&social.link) {
+                                        html! {
+                                            <BookingLink href={social.link.clone()} target="_blank" onclick={onclick} class={classes!(class)}>
+                                                { content }
+                                            </BookingLink>
+                                        }
+                                    } else {
+                                        html! {
+                                            <a href={social.link.clone()} target="_blank" onclick={onclick} class={class}>
+                                                { content }
+                                            </a>
+                                        }
                                     }
                                 }) }
                             </div>
@@ -585,21 +1673,29 @@ fn contact_section(props: &ContactSectionProps) -> Html {
                     </div>
 
                     // Карта
-                    <div class="bg-gradient-to-br from-amber-50 to-orange-100 rounded-2xl p-2 shadow-lg h-full min-h-[500px]">
-                        <div class="w-full h-full rounded-xl overflow-hidden">
-                            <iframe
-                                src={contact.map_url.clone()}
-                                width="100%"
-                                height="100%"
-                                style="border:0; min-height: 480px;"
-                                allowfullscreen={true}
-                                loading="lazy"
-                                referrerpolicy="no-referrer-when-downgrade"
-                            ></iframe>
+                    <div class="space-y-4">
+                        <MapEmbed location={contact.location.clone()} />
+                        <div class="flex flex-wrap gap-3 justify-center">
+                            { for contact.location.route_links().into_iter().map(|route| html! {
+                                <a
+                                    href={route.url}
+                                    target="_blank"
+                                    rel="noopener noreferrer"
+                                    onclick={analytics::on_click(analytics::Goal::Route { service: route.name })}
+                                    class="inline-flex items-center bg-white text-amber-700 px-5 py-2 rounded-full font-body font-semibold shadow hover:shadow-lg hover:bg-amber-50 transition-all motion-reduce:transition-none duration-300"
+                                >
+                                    <i class="fas fa-route mr-2" aria-hidden="true"></i>
+                                    { route.name }
+                                </a>
+                            }) }
                         </div>
                     </div>
                 </div>
 
+                { for contact.directions.as_ref().map(|directions| html! {
+                    <DirectionsGuide directions={directions.clone()} />
+                }) }
+
                 // Кнопка "Наверх"
                 <div class="text-center mt-12">
                     <button
@@ -611,9 +1707,9 @@ fn contact_section(props: &ContactSectionProps) -> Html {
                                         .map(|e| e.scroll_to_with_x_and_y(0.0, 0.0))
                                 });
                         })}
-                        class="bg-amber-600 text-white px-8 py-3 rounded-full font-body font-semibold hover:bg-amber-700 transition-all duration-300 shadow-lg inline-flex items-center"
+                        class="bg-amber-600 text-white px-8 py-3 rounded-full font-body font-semibold hover:bg-amber-700 transition-all motion-reduce:transition-none duration-300 shadow-lg inline-flex items-center"
                     >
-                        <i class="fas fa-arrow-up mr-2"></i>
+                        <i class="fas fa-arrow-up mr-2" aria-hidden="true"></i>
                         { "Наверх" }
                     </button>
                 </div>
@@ -622,10 +1718,124 @@ fn contact_section(props: &ContactSectionProps) -> Html {
     }
 }
 
+#[derive(Properties, PartialEq)]
+struct ConsentBannerProps {
+    text: String,
+    on_choice: Callback<analytics::Consent>,
+}
+
+/// Согласие на cookie и аналитику (152-ФЗ): до выбора скрипт счётчика не подключается.
+#[function_component(ConsentBanner)]
+fn consent_banner(props: &ConsentBannerProps) -> Html {
+    let choice = |consent: analytics::Consent| {
+        let on_choice = props.on_choice.clone();
+        Callback::from(move |_: MouseEvent| on_choice.emit(consent))
+    };
+
+    html! {
+        <div
+            role="dialog"
+            aria-live="polite"
+            aria-label="Согласие на использование cookie"
+            class="fixed bottom-4 inset-x-4 md:left-auto md:max-w-md z-50 bg-white rounded-2xl shadow-2xl border border-amber-200 p-6 font-body"
+        >
+            <div class="text-amber-800 text-sm space-y-2 mb-4">
+                { markdown::render(&props.text) }
+            </div>
+            <div class="flex gap-3">
+                <button
+                    onclick={choice(analytics::Consent::Accepted)}
+                    class="flex-1 bg-amber-600 text-white py-2 rounded-full font-semibold hover:bg-amber-700 transition-colors"
+                >
+                    { "Принять" }
+                </button>
+                <button
+                    onclick={choice(analytics::Consent::Declined)}
+                    class="flex-1 bg-amber-100 text-amber-700 py-2 rounded-full font-semibold hover:bg-amber-200 transition-colors"
+                >
+                    { "Отказаться" }
+                </button>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct InstallBannerProps {
+    info: data::SalonInfo,
+}
+
+/// Предложение установить сайт на главный экран, когда браузер это поддерживает.
+#[function_component(InstallBanner)]
+fn install_banner(props: &InstallBannerProps) -> Html {
+    let prompt = use_state(|| None::<pwa::InstallPrompt>);
+
+    {
+        let prompt = prompt.clone();
+        use_effect_with((), move |_| {
+            let listener = pwa::listen_install_prompt(move |event| prompt.set(Some(event)));
+            move || drop(listener)
+        });
+    }
+
+    let Some(event) = (*prompt).clone() else {
+        return html! {};
+    };
+
+    let oninstall = {
+        let prompt = prompt.clone();
+        Callback::from(move |_: MouseEvent| {
+            let prompt = prompt.clone();
+            let event = event.clone();
+            spawn_local(async move {
+                match event.show().await {
+                    Ok(true) => {}
+                    Ok(false) => pwa::dismiss_install(),
+                    Err(e) => gloo_console::warn!(e),
+                }
+                // Событие одноразовое: повторно браузер его пришлёт сам
+                prompt.set(None);
+            });
+        })
+    };
+    let onclose = {
+        let prompt = prompt.clone();
+        Callback::from(move |_: MouseEvent| {
+            pwa::dismiss_install();
+            prompt.set(None);
+        })
+    };
+
+    html! {
+        <div class="fixed top-4 inset-x-4 md:left-auto md:max-w-sm z-50 bg-white rounded-2xl shadow-2xl border border-amber-200 p-4 font-body flex items-center gap-4">
+            <img src="/static/pwa/icon.svg" alt="" class="w-12 h-12 rounded-xl flex-shrink-0" />
+            <div class="flex-1 text-sm text-amber-800">
+                <p class="font-semibold text-amber-900">{ format!("Приложение «{}»", props.info.name) }</p>
+                <p>{ "Контакты и цены будут под рукой даже без интернета" }</p>
+            </div>
+            <button
+                onclick={oninstall}
+                class="bg-amber-600 text-white px-4 py-2 rounded-full font-semibold hover:bg-amber-700 transition-colors"
+            >
+                { "Установить" }
+            </button>
+            <button onclick={onclose} aria-label="Не сейчас" class="text-amber-400 hover:text-amber-700">
+                <i class="fas fa-times" aria-hidden="true"></i>
+            </button>
+        </div>
+    }
+}
+
 #[derive(Properties, PartialEq)]
 struct FooterProps {
     footer: data::FooterSection,
     contacts: data::Contacts,
+    /// Юридические документы: ссылки на них добавляются к ссылкам подвала.
+    #[prop_or_default]
+    legal: Vec<data::LegalDocument>,
+    /// Повторно показать баннер согласия на аналитику.
+    #[prop_or_default]
+    on_cookie_settings: Option<Callback<MouseEvent>>,
 }
 
 #[function_component(Footer)]
@@ -638,19 +1848,19 @@ fn footer(props: &FooterProps) -> Html {
                 <div class="grid md:grid-cols-3 gap-8 mb-8">
                     // О студии
                     <div>
-                        <h3 class="text-2xl font-heading font-bold mb-4">
+                        <h2 class="text-2xl font-heading font-bold mb-4">
                             { "Я Инна" }
-                        </h3>
-                        <p class="text-white/80 font-body leading-relaxed">
-                            { &footer.description }
-                        </p>
+                        </h2>
+                        <div class="text-white/80 font-body leading-relaxed space-y-2">
+                            { markdown::render(&footer.description) }
+                        </div>
                     </div>
 
                     // Быстрые ссылки
-                    <div>
-                        <h3 class="text-xl font-heading font-bold mb-4">
+                    <nav aria-labelledby="footer-links-title">
+                        <h2 id="footer-links-title" class="text-xl font-heading font-bold mb-4">
                             { "Информация" }
-                        </h3>
+                        </h2>
                         <ul class="space-y-2">
                             { for footer.links.iter().map(|link| {
                                 html! {
@@ -661,29 +1871,43 @@ fn footer(props: &FooterProps) -> Html {
                                     </li>
                                 }
                             }) }
+                            { for props.legal.iter().map(|document| html! {
+                                <li>
+                                    <a href={legal::page_url(document)} class="text-white/80 hover:text-white transition-colors font-body">
+                                        { &document.title }
+                                    </a>
+                                </li>
+                            }) }
+                            { for props.on_cookie_settings.clone().map(|onclick| html! {
+                                <li>
+                                    <button type="button" onclick={onclick} class="text-white/80 hover:text-white transition-colors font-body">
+                                        { "Настройки cookie" }
+                                    </button>
+                                </li>
+                            }) }
                         </ul>
-                    </div>
+                    </nav>
 
                     // Студия
                     <div>
-                        <h3 class="text-xl font-heading font-bold mb-4">
+                        <h2 class="text-xl font-heading font-bold mb-4">
                             { "Студия красоты" }
-                        </h3>
+                        </h2>
                         <p class="text-white/80 font-body mb-4">
                             { "Сочи, Хостинский район" }
                         </p>
                         <div class="flex gap-4">
-                            <a href={props.contacts.instagram.clone()} target="_blank" class="text-white/80 hover:text-white transition-colors">
-                                <i class="fab fa-instagram text-2xl"></i>
-                            </a>
-                            <a href={props.contacts.telegram.clone()} target="_blank" class="text-white/80 hover:text-white transition-colors">
-                                <i class="fab fa-telegram text-2xl"></i>
-                            </a>
-                            <a href={props.contacts.whatsapp.clone()} target="_blank" class="text-white/80 hover:text-white transition-colors">
-                                <i class="fab fa-whatsapp text-2xl"></i>
+                            <a href={props.contacts.instagram.clone()} target="_blank" aria-label="Instagram" onclick={analytics::on_click(analytics::Goal::Social { network: "Instagram".to_string() })} class="text-white/80 hover:text-white transition-colors">
+                                <i class="fab fa-instagram text-2xl" aria-hidden="true"></i>
                             </a>
-                            <a href={props.contacts.vk.clone()} target="_blank" class="text-white/80 hover:text-white transition-colors">
-                                <i class="fab fa-vk text-2xl"></i>
+                            <BookingLink href={props.contacts.telegram.clone()} target="_blank" aria_label="Telegram" onclick={analytics::on_click(analytics::Goal::Social { network: "Telegram".to_string() })} class="text-white/80 hover:text-white transition-colors">
+                                <i class="fab fa-telegram text-2xl" aria-hidden="true"></i>
+                            </BookingLink>
+                            <BookingLink href={props.contacts.whatsapp.clone()} target="_blank" aria_label="WhatsApp" onclick={analytics::on_click(analytics::Goal::Social { network: "WhatsApp".to_string() })} class="text-white/80 hover:text-white transition-colors">
+                                <i class="fab fa-whatsapp text-2xl" aria-hidden="true"></i>
+                            </BookingLink>
+                            <a href={props.contacts.vk.clone()} target="_blank" aria-label="VK" onclick={analytics::on_click(analytics::Goal::Social { network: "VK".to_string() })} class="text-white/80 hover:text-white transition-colors">
+                                <i class="fab fa-vk text-2xl" aria-hidden="true"></i>
                             </a>
                         </div>
                     </div>
@@ -701,5 +1925,11 @@ fn footer(props: &FooterProps) -> Html {
 }
 
 fn main() {
+    // Нативная сборка с аргументами работает как консольная утилита для контента
+    #[cfg(all(not(target_arch = "wasm32"), feature = "yaml"))]
+    if std::env::args().len() > 1 {
+        std::process::exit(cli::run(std::env::args().skip(1).collect()));
+    }
+
     yew::Renderer::<App>::new().render();
 }
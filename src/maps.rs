@@ -0,0 +1,161 @@
+use crate::data::Location;
+use crate::url_query::encode_component;
+
+/// Ссылка на маршрут в картографическом сервисе.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLink {
+    pub name: &'static str,
+    pub url: String,
+}
+
+impl Location {
+    /// `55.75,37.61` — широта и долгота.
+    fn lat_lon(&self) -> String {
+        format!("{},{}", self.lat, self.lon)
+    }
+
+    /// `37.61,55.75` — порядок, принятый в Яндекс Картах и 2ГИС.
+    fn lon_lat(&self) -> String {
+        format!("{},{}", self.lon, self.lat)
+    }
+
+    /// Адрес виджета Яндекс Карт для iframe.
+    pub fn iframe_url(&self) -> String {
+        let point = self.lon_lat();
+        match &self.yandex_org_id {
+            Some(oid) => format!(
+                "https://yandex.ru/map-widget/v1/?ll={}&z={}&mode=poi&poi%5Bpoint%5D={}&poi%5Buri%5D={}",
+                encode_component(&point),
+                self.zoom,
+                encode_component(&point),
+                encode_component(&format!("ymapsbm1://org?oid={}", oid)),
+            ),
+            None => format!(
+                "https://yandex.ru/map-widget/v1/?ll={}&z={}&pt={}",
+                encode_component(&point),
+                self.zoom,
+                encode_component(&format!("{},pm2rdm", point)),
+            ),
+        }
+    }
+
+    /// Картинка карты без скриптов: своя `map_image`, а если её нет и включён
+    /// `yandex_static_map` — статичная карта Яндекса. `None` — картинки нет.
+    pub fn map_image_url(&self) -> Option<String> {
+        self.map_image.clone().or_else(|| {
+            self.yandex_static_map.then(|| {
+                format!(
+                    "https://static-maps.yandex.ru/1.x/?ll={}&z={}&size=650,450&l=map&pt={},pm2rdm",
+                    self.lon_lat(),
+                    self.zoom.min(17),
+                    self.lon_lat()
+                )
+            })
+        })
+    }
+
+    /// Маршрут до студии от текущего места в Яндекс Картах, Google Maps и 2ГИС.
+    pub fn route_links(&self) -> [RouteLink; 3] {
+        [
+            RouteLink {
+                name: "Яндекс Карты",
+                url: format!("https://yandex.ru/maps/?rtext=~{}&rtt=auto", self.lat_lon()),
+            },
+            RouteLink {
+                name: "Google Maps",
+                url: format!(
+                    "https://www.google.com/maps/dir/?api=1&destination={}",
+                    self.lat_lon()
+                ),
+            },
+            RouteLink {
+                name: "2ГИС",
+                url: format!(
+                    "https://2gis.ru/routeSearch/rsType/car/to/{}",
+                    self.lon_lat()
+                ),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location() -> Location {
+        Location {
+            address: "г. Сочи, ул. Платановая, д. 15/1".to_string(),
+            lat: 43.511137,
+            lon: 39.866547,
+            zoom: 18,
+            yandex_org_id: None,
+            entrance: None,
+            entrance_photo: None,
+            entrance_photo_alt: None,
+            map_image: None,
+            yandex_static_map: false,
+        }
+    }
+
+    #[test]
+    fn iframe_puts_longitude_first_and_encodes_the_point() {
+        let mut location = location();
+        assert_eq!(
+            location.iframe_url(),
+            "https://yandex.ru/map-widget/v1/?ll=39.866547%2C43.511137&z=18\
+             &pt=39.866547%2C43.511137%2Cpm2rdm"
+        );
+
+        location.yandex_org_id = Some("195016917260".to_string());
+        assert_eq!(
+            location.iframe_url(),
+            "https://yandex.ru/map-widget/v1/?ll=39.866547%2C43.511137&z=18&mode=poi\
+             &poi%5Bpoint%5D=39.866547%2C43.511137\
+             &poi%5Buri%5D=ymapsbm1%3A%2F%2Forg%3Foid%3D195016917260"
+        );
+    }
+
+    #[test]
+    fn route_links_use_each_service_coordinate_order() {
+        let location = Location {
+            lat: -33.8688,
+            lon: 151.2093,
+            ..location()
+        };
+        let urls = location
+            .route_links()
+            .map(|route| route.url)
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            urls,
+            [
+                "https://yandex.ru/maps/?rtext=~-33.8688,151.2093&rtt=auto",
+                "https://www.google.com/maps/dir/?api=1&destination=-33.8688,151.2093",
+                "https://2gis.ru/routeSearch/rsType/car/to/151.2093,-33.8688",
+            ]
+        );
+    }
+
+    #[test]
+    fn map_image_is_local_unless_the_static_map_is_enabled() {
+        let mut location = location();
+        assert_eq!(location.map_image_url(), None);
+
+        location.yandex_static_map = true;
+        assert_eq!(
+            location.map_image_url().as_deref(),
+            Some(
+                "https://static-maps.yandex.ru/1.x/?ll=39.866547,43.511137&z=17\
+                 &size=650,450&l=map&pt=39.866547,43.511137,pm2rdm"
+            )
+        );
+
+        location.map_image = Some("/static/images/map.png".to_string());
+        assert_eq!(
+            location.map_image_url().as_deref(),
+            Some("/static/images/map.png")
+        );
+    }
+}
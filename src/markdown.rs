@@ -0,0 +1,509 @@
+use yew::prelude::*;
+
+/// Разрешённые схемы ссылок; остальные (`javascript:`, `data:` и т.п.)
+/// выводятся обычным текстом.
+const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+/// Блок описания. Поддерживается подмножество Markdown: заголовки (`# `…`### `),
+/// абзацы, переносы строк, **жирный**, *курсив*, списки (`- `, `* `, `1. `)
+/// и [ссылки](https://...).
+/// Разметка собирается из узлов Yew, без `inner_html`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Heading {
+        level: u8,
+        inlines: Vec<Inline>,
+    },
+    Paragraph(Vec<Inline>),
+    List {
+        ordered: bool,
+        items: Vec<Vec<Inline>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inline {
+    Text(String),
+    Strong(Vec<Inline>),
+    Emphasis(Vec<Inline>),
+    Link { href: String, children: Vec<Inline> },
+    LineBreak,
+}
+
+/// Текст описания в виде абзацев и списков. Оборачивающий элемент
+/// задаёт цвет и размер шрифта, отступы между блоками — `space-y-*`.
+pub fn render(text: &str) -> Html {
+    parse(text).iter().map(render_block).collect()
+}
+
+/// Текст без разметки: для структурированных данных и других мест, где
+/// Markdown показался бы звёздочками. Блоки разделяются пустой строкой.
+pub fn plain_text(text: &str) -> String {
+    parse(text)
+        .iter()
+        .map(|block| match block {
+            Block::Heading { inlines, .. } | Block::Paragraph(inlines) => plain_inlines(inlines),
+            Block::List { ordered, items } => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let marker = if *ordered {
+                        format!("{}.", i + 1)
+                    } else {
+                        "•".to_string()
+                    };
+                    format!("{} {}", marker, plain_inlines(item))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn plain_inlines(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Text(text) => text.clone(),
+            Inline::Strong(children)
+            | Inline::Emphasis(children)
+            | Inline::Link { children, .. } => plain_inlines(children),
+            Inline::LineBreak => "\n".to_string(),
+        })
+        .collect()
+}
+
+pub fn parse(text: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Option<(bool, Vec<Vec<&str>>)> = None;
+
+    let flush_paragraph = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(parse_inline(&paragraph.join("\n"))));
+            paragraph.clear();
+        }
+    };
+    let flush_list = |list: &mut Option<(bool, Vec<Vec<&str>>)>, blocks: &mut Vec<Block>| {
+        if let Some((ordered, items)) = list.take() {
+            blocks.push(Block::List {
+                ordered,
+                items: items
+                    .iter()
+                    .map(|lines| parse_inline(&lines.join("\n")))
+                    .collect(),
+            });
+        }
+    };
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            flush_list(&mut list, &mut blocks);
+            continue;
+        }
+
+        if let Some((level, title)) = heading(line) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            flush_list(&mut list, &mut blocks);
+            blocks.push(Block::Heading {
+                level,
+                inlines: parse_inline(title),
+            });
+            continue;
+        }
+
+        if let Some((ordered, item)) = list_item(line) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            match &mut list {
+                Some((current, items)) if *current == ordered => items.push(vec![item]),
+                _ => {
+                    flush_list(&mut list, &mut blocks);
+                    list = Some((ordered, vec![vec![item]]));
+                }
+            }
+            continue;
+        }
+
+        // Строка с отступом продолжает пункт списка, без отступа — начинает абзац.
+        match &mut list {
+            Some((_, items)) if line.starts_with([' ', '\t']) => {
+                if let Some(last) = items.last_mut() {
+                    last.push(line.trim());
+                }
+            }
+            _ => {
+                flush_list(&mut list, &mut blocks);
+                paragraph.push(line.trim());
+            }
+        }
+    }
+
+    flush_paragraph(&mut paragraph, &mut blocks);
+    flush_list(&mut list, &mut blocks);
+    blocks
+}
+
+/// Ссылка, если её схема безопасна; относительные адреса и якоря разрешены.
+pub fn safe_href(href: &str) -> Option<String> {
+    let href = href.trim();
+    // Браузеры игнорируют пробелы и управляющие символы внутри схемы: «java\tscript:».
+    let compact = href
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+
+    let scheme = compact.split_once(':').map(|(scheme, _)| scheme);
+    let has_scheme = scheme.is_some_and(|scheme| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+
+    match scheme {
+        Some(scheme) if has_scheme && !SAFE_SCHEMES.contains(&scheme) => None,
+        _ if href.is_empty() => None,
+        _ => Some(href.to_string()),
+    }
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let title = line[level..].strip_prefix(' ')?;
+    (1..=3)
+        .contains(&level)
+        .then_some((level as u8, title.trim()))
+}
+
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    if let Some(item) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("• "))
+    {
+        return Some((false, item.trim()));
+    }
+
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let rest = &trimmed[digits..];
+    let item = rest
+        .strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))?;
+    (digits > 0).then_some((true, item.trim()))
+}
+
+fn parse_inline(text: &str) -> Vec<Inline> {
+    let chars = text.chars().collect::<Vec<_>>();
+    parse_span(&chars)
+}
+
+fn parse_span(chars: &[char]) -> Vec<Inline> {
+    let mut result = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+
+    let push_node = |text: &mut String, result: &mut Vec<Inline>, node: Inline| {
+        if !text.is_empty() {
+            result.push(Inline::Text(std::mem::take(text)));
+        }
+        result.push(node);
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '\\' if next.is_some_and(|n| n.is_ascii_punctuation()) => {
+                text.extend(next);
+                i += 2;
+            }
+            '\n' => {
+                push_node(&mut text, &mut result, Inline::LineBreak);
+                i += 1;
+            }
+            '*' | '_' if next == Some(c) => match find(chars, i + 2, &[c, c]) {
+                Some(end) if end > i + 2 => {
+                    let children = parse_span(&chars[i + 2..end]);
+                    push_node(&mut text, &mut result, Inline::Strong(children));
+                    i = end + 2;
+                }
+                _ => {
+                    text.push_str(&format!("{c}{c}"));
+                    i += 2;
+                }
+            },
+            // `_` внутри слова (snake_case) курсивом не считается.
+            '*' | '_'
+                if !(c == '_' && i > 0 && chars[i - 1].is_alphanumeric())
+                    && next.is_some_and(|n| !n.is_whitespace()) =>
+            {
+                match find(chars, i + 1, &[c]) {
+                    Some(end) => {
+                        let children = parse_span(&chars[i + 1..end]);
+                        push_node(&mut text, &mut result, Inline::Emphasis(children));
+                        i = end + 1;
+                    }
+                    None => {
+                        text.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            '[' => match link(chars, i) {
+                Some((label, href, end)) => {
+                    let children = parse_span(label);
+                    match safe_href(&href) {
+                        Some(href) => {
+                            push_node(&mut text, &mut result, Inline::Link { href, children })
+                        }
+                        // Опасная ссылка остаётся просто текстом.
+                        None => {
+                            if !text.is_empty() {
+                                result.push(Inline::Text(std::mem::take(&mut text)));
+                            }
+                            result.extend(children);
+                        }
+                    }
+                    i = end;
+                }
+                None => {
+                    text.push(c);
+                    i += 1;
+                }
+            },
+            _ => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        result.push(Inline::Text(text));
+    }
+    result
+}
+
+/// Позиция закрывающего маркера, не экранированного обратным слешем.
+fn find(chars: &[char], from: usize, marker: &[char]) -> Option<usize> {
+    let mut i = from;
+    while i + marker.len() <= chars.len() {
+        if chars[i] == '\\' {
+            i += 2;
+            continue;
+        }
+        if chars[i..].starts_with(marker) {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// `[текст](адрес)`: текст, адрес и позиция после закрывающей скобки.
+fn link(chars: &[char], start: usize) -> Option<(&[char], String, usize)> {
+    let label_end = find(chars, start + 1, &[']'])?;
+    if chars.get(label_end + 1) != Some(&'(') {
+        return None;
+    }
+    // Скобки внутри адреса допустимы, если они парные: `https://ru.wikipedia.org/wiki/Хна_(краситель)`.
+    let mut depth = 0;
+    let href_end = (label_end + 2..chars.len()).find(|&i| {
+        match chars[i] {
+            '(' => depth += 1,
+            ')' if depth == 0 => return true,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        false
+    })?;
+    let href = chars[label_end + 2..href_end].iter().collect::<String>();
+    Some((&chars[start + 1..label_end], href, href_end + 1))
+}
+
+fn render_block(block: &Block) -> Html {
+    match block {
+        // Заголовки описаний идут внутри секции с <h2>, поэтому уровни сдвинуты на один
+        Block::Heading { level: 1, inlines } => html! {
+            <h2 class="text-2xl font-heading font-bold text-amber-900 pt-4">{ render_inlines(inlines) }</h2>
+        },
+        Block::Heading { level: 2, inlines } => html! {
+            <h3 class="text-xl font-heading font-bold text-amber-900 pt-2">{ render_inlines(inlines) }</h3>
+        },
+        Block::Heading { inlines, .. } => html! {
+            <h4 class="text-lg font-heading font-semibold text-amber-900">{ render_inlines(inlines) }</h4>
+        },
+        Block::Paragraph(inlines) => html! { <p>{ render_inlines(inlines) }</p> },
+        Block::List {
+            ordered: false,
+            items,
+        } => html! {
+            <ul class="list-disc pl-5 space-y-1 text-left">
+                { for items.iter().map(|item| html! { <li>{ render_inlines(item) }</li> }) }
+            </ul>
+        },
+        Block::List {
+            ordered: true,
+            items,
+        } => html! {
+            <ol class="list-decimal pl-5 space-y-1 text-left">
+                { for items.iter().map(|item| html! { <li>{ render_inlines(item) }</li> }) }
+            </ol>
+        },
+    }
+}
+
+fn render_inlines(inlines: &[Inline]) -> Html {
+    inlines.iter().map(render_inline).collect()
+}
+
+fn render_inline(inline: &Inline) -> Html {
+    match inline {
+        Inline::Text(text) => html! { { text } },
+        Inline::Strong(children) => html! { <strong>{ render_inlines(children) }</strong> },
+        Inline::Emphasis(children) => html! { <em>{ render_inlines(children) }</em> },
+        Inline::LineBreak => html! { <br /> },
+        Inline::Link { href, children } => {
+            let external = href.starts_with("http://") || href.starts_with("https://");
+            html! {
+                <a
+                    href={href.clone()}
+                    target={external.then_some("_blank")}
+                    rel={external.then_some("noopener noreferrer")}
+                    class="underline underline-offset-2 hover:opacity-80"
+                >
+                    { render_inlines(children) }
+                </a>
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inlines(blocks: &[Block]) -> Vec<&Inline> {
+        fn walk<'a>(inlines: &'a [Inline], out: &mut Vec<&'a Inline>) {
+            for inline in inlines {
+                out.push(inline);
+                if let Inline::Strong(children)
+                | Inline::Emphasis(children)
+                | Inline::Link { children, .. } = inline
+                {
+                    walk(children, out);
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        for block in blocks {
+            match block {
+                Block::Heading { inlines, .. } | Block::Paragraph(inlines) => {
+                    walk(inlines, &mut out)
+                }
+                Block::List { items, .. } => items.iter().for_each(|item| walk(item, &mut out)),
+            }
+        }
+        out
+    }
+
+    fn text(blocks: &[Block]) -> String {
+        inlines(blocks)
+            .into_iter()
+            .filter_map(|inline| match inline {
+                Inline::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rejects_script_schemes() {
+        for href in [
+            "javascript:alert(1)",
+            "JaVa\tScript:alert(1)",
+            " javascript:alert(1)",
+            "java\nscript:alert(1)",
+            "vbscript:msgbox(1)",
+            "data:text/html,<script>alert(1)</script>",
+            "DATA:image/svg+xml;base64,PHN2Zz4=",
+            "",
+            "   ",
+        ] {
+            assert_eq!(safe_href(href), None, "{:?}", href);
+        }
+    }
+
+    #[test]
+    fn allows_safe_links() {
+        for href in [
+            "https://yainna.ru",
+            "HTTP://example.com",
+            "/?page=privacy",
+            "price.html",
+            "../images/photo.jpg",
+            "#faq-price",
+            "mailto:inna@example.com",
+            "tel:+79001234567",
+        ] {
+            assert_eq!(safe_href(href).as_deref(), Some(href), "{:?}", href);
+        }
+    }
+
+    #[test]
+    fn unsafe_link_becomes_text() {
+        let blocks = parse("Нажмите [сюда](javascript:alert(1)) сейчас");
+        assert!(
+            !inlines(&blocks)
+                .iter()
+                .any(|inline| matches!(inline, Inline::Link { .. }))
+        );
+        assert_eq!(text(&blocks), "Нажмите сюда сейчас");
+    }
+
+    #[test]
+    fn html_stays_text() {
+        let source = "<script>alert(1)</script>\n\n**<img src=x onerror=alert(1)>**\n\n- <b>пункт</b>\n\n# <h1>";
+        let blocks = parse(source);
+
+        // Разметка в исходнике доходит до Yew только как текст, который он экранирует.
+        assert_eq!(
+            text(&blocks),
+            "<script>alert(1)</script><img src=x onerror=alert(1)><b>пункт</b><h1>"
+        );
+        for inline in inlines(&blocks) {
+            if let Inline::Link { href, .. } = inline {
+                assert_eq!(safe_href(href).as_ref(), Some(href));
+            }
+        }
+    }
+
+    #[test]
+    fn plain_text_drops_markup() {
+        assert_eq!(
+            plain_text(
+                "**Да**, [пишите](https://t.me/yainna)\\*\n\n- утром\n- *вечером*\n\n1. раз"
+            ),
+            "Да, пишите*\n\n• утром\n• вечером\n\n1. раз"
+        );
+    }
+
+    #[test]
+    fn links_keep_safe_href() {
+        let blocks = parse("[Политика](/?page=privacy) и [почта](mailto:inna@example.com)");
+        let hrefs = inlines(&blocks)
+            .into_iter()
+            .filter_map(|inline| match inline {
+                Inline::Link { href, .. } => Some(href.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(hrefs, ["/?page=privacy", "mailto:inna@example.com"]);
+    }
+}
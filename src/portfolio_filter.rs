@@ -0,0 +1,242 @@
+use crate::data::{FilterMode, PortfolioItem};
+use crate::url_query;
+
+/// Каждый тег — отдельный параметр `tag=`, поэтому в теге может быть запятая.
+const TAG_PARAM: &str = "tag";
+const MODE_PARAM: &str = "mode";
+const QUERY_PARAM: &str = "q";
+
+/// Состояние фильтра галереи: выбранные теги, режим их сочетания и строка поиска.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioFilter {
+    pub tags: Vec<String>,
+    pub mode: FilterMode,
+    pub query: String,
+}
+
+impl PortfolioFilter {
+    pub fn new(mode: FilterMode) -> Self {
+        Self {
+            tags: Vec::new(),
+            mode,
+            query: String::new(),
+        }
+    }
+
+    pub fn is_selected(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    pub fn toggle_tag(&self, tag: &str) -> Self {
+        let mut next = self.clone();
+        if next.is_selected(tag) {
+            next.tags.retain(|t| t != tag);
+        } else {
+            next.tags.push(tag.to_string());
+        }
+        next
+    }
+
+    pub fn clear_tags(&self) -> Self {
+        Self {
+            tags: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    pub fn with_mode(&self, mode: FilterMode) -> Self {
+        Self {
+            mode,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_query(&self, query: String) -> Self {
+        Self {
+            query,
+            ..self.clone()
+        }
+    }
+
+    pub fn matches(&self, item: &PortfolioItem) -> bool {
+        let item_tags = item.tags();
+        let tags_match = match self.mode {
+            _ if self.tags.is_empty() => true,
+            FilterMode::Any => self.tags.iter().any(|t| item_tags.contains(t)),
+            FilterMode::All => self.tags.iter().all(|t| item_tags.contains(t)),
+        };
+
+        let query = self.query.trim().to_lowercase();
+        let query_match = query.is_empty()
+            || item.title.to_lowercase().contains(&query)
+            || item.description.to_lowercase().contains(&query);
+
+        tags_match && query_match
+    }
+
+    /// Восстанавливает фильтр из query-строки текущего адреса.
+    pub fn from_location(default_mode: FilterMode) -> Self {
+        let params = [TAG_PARAM, MODE_PARAM, QUERY_PARAM]
+            .into_iter()
+            .flat_map(|name| {
+                url_query::get_list(name)
+                    .into_iter()
+                    .map(move |value| (name, value))
+            })
+            .collect::<Vec<_>>();
+        Self::from_params(default_mode, &params)
+    }
+
+    /// Фильтр из параметров адреса в порядке их следования.
+    fn from_params(default_mode: FilterMode, params: &[(&str, String)]) -> Self {
+        let values = |name: &'static str| {
+            params
+                .iter()
+                .filter(move |(key, _)| *key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let mut filter = Self::new(default_mode);
+
+        filter.tags = values(TAG_PARAM)
+            .filter(|t| !t.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some(mode) = values(MODE_PARAM).next() {
+            filter.mode = match mode {
+                "all" => FilterMode::All,
+                "any" => FilterMode::Any,
+                _ => default_mode,
+            };
+        }
+        if let Some(query) = values(QUERY_PARAM).next() {
+            filter.query = query.to_string();
+        }
+        filter
+    }
+
+    /// Записывает фильтр в адресную строку, не трогая чужие параметры.
+    pub fn write_to_location(&self, default_mode: FilterMode) {
+        let query = self.query.trim();
+
+        url_query::set_list(TAG_PARAM, &self.tags);
+        url_query::set_all(&[
+            (MODE_PARAM, self.mode_param(default_mode)),
+            (QUERY_PARAM, Some(query).filter(|q| !q.is_empty())),
+        ]);
+    }
+
+    /// Режим попадает в адрес, только если он отличается от режима по умолчанию.
+    fn mode_param(&self, default_mode: FilterMode) -> Option<&'static str> {
+        Some(self.mode.as_param()).filter(|_| self.mode != default_mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(category: &str, tags: &[&str], title: &str, description: &str) -> PortfolioItem {
+        PortfolioItem {
+            image: None,
+            media: Vec::new(),
+            alt: None,
+            category: category.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            service_ids: Vec::new(),
+            title: title.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    fn params(pairs: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (*name, value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_repeated_tags() {
+        let filter = PortfolioFilter::from_params(
+            FilterMode::Any,
+            &params(&[("tag", "Брови"), ("tag", "Макияж, вечерний"), ("tag", " ")]),
+        );
+        assert_eq!(filter.tags, ["Брови", "Макияж, вечерний"]);
+        assert_eq!(filter.mode, FilterMode::Any);
+        assert_eq!(filter.query, "");
+    }
+
+    #[test]
+    fn parses_mode_and_query() {
+        let filter = PortfolioFilter::from_params(
+            FilterMode::Any,
+            &params(&[("mode", "all"), ("q", "свадьба")]),
+        );
+        assert_eq!(filter.mode, FilterMode::All);
+        assert_eq!(filter.query, "свадьба");
+
+        let unknown = params(&[("mode", "some")]);
+        assert_eq!(
+            PortfolioFilter::from_params(FilterMode::All, &unknown).mode,
+            FilterMode::All
+        );
+    }
+
+    #[test]
+    fn default_mode_is_not_written() {
+        let filter = PortfolioFilter::new(FilterMode::Any);
+        assert_eq!(filter.mode_param(FilterMode::Any), None);
+        assert_eq!(
+            filter
+                .with_mode(FilterMode::All)
+                .mode_param(FilterMode::Any),
+            Some("all")
+        );
+    }
+
+    #[test]
+    fn toggles_tags() {
+        let filter = PortfolioFilter::new(FilterMode::Any).toggle_tag("Брови");
+        assert!(filter.is_selected("Брови"));
+        assert!(!filter.toggle_tag("Брови").is_selected("Брови"));
+        assert!(filter.clear_tags().tags.is_empty());
+    }
+
+    #[test]
+    fn matches_any_or_all_tags() {
+        let work = item("Брови", &["Брови", "Окрашивание"], "Брови хной", "");
+        let any = PortfolioFilter::new(FilterMode::Any)
+            .toggle_tag("Окрашивание")
+            .toggle_tag("Ресницы");
+        assert!(any.matches(&work));
+        assert!(!any.with_mode(FilterMode::All).matches(&work));
+        assert!(PortfolioFilter::new(FilterMode::All).matches(&work));
+    }
+
+    #[test]
+    fn category_is_the_tag_by_default() {
+        let work = item("Ресницы", &[], "Ламинирование", "");
+        let filter = PortfolioFilter::new(FilterMode::Any).toggle_tag("Ресницы");
+        assert!(filter.matches(&work));
+    }
+
+    #[test]
+    fn query_searches_title_and_description() {
+        let work = item(
+            "Макияж",
+            &[],
+            "Вечерний образ",
+            "Стойкий макияж для СВАДЬБЫ",
+        );
+        let filter = PortfolioFilter::new(FilterMode::Any);
+        assert!(filter.with_query("  вечерний ".to_string()).matches(&work));
+        assert!(filter.with_query("свадьбы".to_string()).matches(&work));
+        assert!(!filter.with_query("брови".to_string()).matches(&work));
+        assert!(
+            !filter
+                .toggle_tag("Брови")
+                .with_query("вечерний".to_string())
+                .matches(&work)
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use crate::data::{Combo, Discount, ServiceItem};
+use crate::text::plural_ru;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+
+/// Цена из прайса. Текст показывается как есть («от 2 500 ₽»), а сумма
+/// в рублях нужна калькулятору; у «по запросу» её нет.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "String")]
+pub struct Price {
+    text: String,
+    amount: Option<u32>,
+    minimum: bool,
+}
+
+impl Price {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn amount(&self) -> Option<u32> {
+        self.amount
+    }
+
+    /// Цена «от»: итог с такой услугой только ориентировочный.
+    pub fn is_minimum(&self) -> bool {
+        self.minimum
+    }
+}
+
+impl From<String> for Price {
+    fn from(text: String) -> Self {
+        let numbers = numbers(&text);
+        let lower = text.to_lowercase();
+        Self {
+            amount: numbers.first().map(|(n, _)| *n as u32),
+            minimum: lower.starts_with("от") || numbers.len() > 1,
+            text,
+        }
+    }
+}
+
+/// Длительность из прайса: «60 минут», «1,5 часа», «1 час 30 минут».
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "String")]
+pub struct Duration {
+    text: String,
+    minutes: Option<u32>,
+}
+
+impl Duration {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn minutes(&self) -> Option<u32> {
+        self.minutes
+    }
+}
+
+impl From<String> for Duration {
+    fn from(text: String) -> Self {
+        let minutes = numbers(&text)
+            .into_iter()
+            .map(|(value, unit)| match unit.chars().next() {
+                Some('ч') | Some('h') => Some(value * 60.0),
+                Some('м') | Some('m') | None => Some(value),
+                _ => None,
+            })
+            .sum::<Option<f32>>()
+            .filter(|minutes| *minutes > 0.0)
+            .map(|minutes| minutes.round() as u32);
+        Self { text, minutes }
+    }
+}
+
+impl Serialize for Price {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Скидка комплекса, применённая к выбору.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedCombo {
+    pub name: String,
+    pub saving: u32,
+}
+
+/// Расчёт стоимости набора услуг.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Quote {
+    pub subtotal: u32,
+    pub combos: Vec<AppliedCombo>,
+    pub total: u32,
+    pub minutes: u32,
+    /// Есть цены «от» или услуги без цены или длительности.
+    pub approximate: bool,
+}
+
+impl Quote {
+    /// Каждая услуга участвует не больше чем в одном комплексе; из пересекающихся
+    /// комплексов выбираются самые выгодные.
+    pub fn new(items: &[&ServiceItem], combos: &[Combo]) -> Self {
+        let price_of = |id: &str| {
+            items
+                .iter()
+                .find(|item| item.id == id)
+                .and_then(|item| item.price.amount())
+                .unwrap_or(0)
+        };
+
+        let mut candidates = combos
+            .iter()
+            .filter(|combo| {
+                combo
+                    .service_ids
+                    .iter()
+                    .all(|id| items.iter().any(|item| &item.id == id))
+            })
+            .map(|combo| {
+                let base = combo.service_ids.iter().map(|id| price_of(id)).sum();
+                (combo, combo.discount.saving(base))
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(_, saving)| std::cmp::Reverse(*saving));
+
+        let mut used: Vec<&str> = Vec::new();
+        let mut applied = Vec::new();
+        for (combo, saving) in candidates {
+            if saving == 0
+                || combo
+                    .service_ids
+                    .iter()
+                    .any(|id| used.contains(&id.as_str()))
+            {
+                continue;
+            }
+            used.extend(combo.service_ids.iter().map(String::as_str));
+            applied.push(AppliedCombo {
+                name: combo.name.clone(),
+                saving,
+            });
+        }
+
+        let subtotal = items.iter().filter_map(|item| item.price.amount()).sum();
+        let discount = applied.iter().map(|combo| combo.saving).sum::<u32>();
+
+        Self {
+            subtotal,
+            total: subtotal.saturating_sub(discount),
+            combos: applied,
+            minutes: items
+                .iter()
+                .filter_map(|item| item.duration.minutes())
+                .sum(),
+            approximate: items.iter().any(|item| {
+                item.price.is_minimum()
+                    || item.price.amount().is_none()
+                    || item.duration.minutes().is_none()
+            }),
+        }
+    }
+
+    pub fn saving(&self) -> u32 {
+        self.subtotal - self.total
+    }
+}
+
+impl Discount {
+    /// Скидка в рублях от суммы услуг комплекса.
+    pub fn saving(&self, base: u32) -> u32 {
+        match self {
+            Discount::Percent(percent) => base * (*percent).min(100) / 100,
+            Discount::Amount(amount) => (*amount).min(base),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Discount::Percent(percent) => format!("−{}%", percent),
+            Discount::Amount(amount) => format!("−{}", format_rub(*amount)),
+        }
+    }
+}
+
+/// «12 500 ₽» с неразрывными пробелами.
+pub fn format_rub(amount: u32) -> String {
+    let digits = amount.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push('\u{a0}');
+        }
+        grouped.push(digit);
+    }
+    format!("{}\u{a0}₽", grouped)
+}
+
+/// «2 часа 30 минут».
+pub fn format_minutes(minutes: u32) -> String {
+    let hours = (minutes / 60) as usize;
+    let rest = (minutes % 60) as usize;
+    let hours_text = format!("{} {}", hours, plural_ru(hours, "час", "часа", "часов"));
+    let minutes_text = format!("{} {}", rest, plural_ru(rest, "минута", "минуты", "минут"));
+    match (hours, rest) {
+        (0, _) => minutes_text,
+        (_, 0) => hours_text,
+        _ => format!("{} {}", hours_text, minutes_text),
+    }
+}
+
+/// Числа в тексте вместе со словом после них: «1 час 30 минут» → (1, «час»), (30, «минут»).
+/// Пробелы внутри числа («1 500») считаются разделителями разрядов.
+fn numbers(text: &str) -> Vec<(f32, String)> {
+    let mut result = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if !c.is_ascii_digit() {
+            chars.next();
+            continue;
+        }
+
+        let mut number = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_ascii_digit() {
+                number.push(c);
+            } else if c == ',' || c == '.' {
+                number.push('.');
+            } else if c.is_whitespace() {
+                // Пробел внутри числа, если после него снова три цифры: «1 500».
+                let mut lookahead = chars.clone();
+                lookahead.next();
+                let group = lookahead.clone().take_while(|c| c.is_ascii_digit()).count();
+                if group != 3 {
+                    break;
+                }
+            } else {
+                break;
+            }
+            chars.next();
+        }
+
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        let unit = chars
+            .clone()
+            .take_while(|c| c.is_alphabetic())
+            .collect::<String>()
+            .to_lowercase();
+
+        if let Ok(value) = number.trim_end_matches('.').parse() {
+            result.push((value, unit));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(text: &str) -> Price {
+        Price::from(text.to_string())
+    }
+
+    fn minutes(text: &str) -> Option<u32> {
+        Duration::from(text.to_string()).minutes()
+    }
+
+    #[test]
+    fn price_with_thousands_separator() {
+        let price = price("от 1 500 ₽");
+        assert_eq!(price.amount(), Some(1500));
+        assert!(price.is_minimum());
+        assert_eq!(price.as_str(), "от 1 500 ₽");
+    }
+
+    #[test]
+    fn plain_and_ranged_prices() {
+        assert_eq!(price("2500 ₽").amount(), Some(2500));
+        assert!(!price("2500 ₽").is_minimum());
+
+        let range = price("1 500 – 2 000 ₽");
+        assert_eq!(range.amount(), Some(1500));
+        assert!(range.is_minimum());
+    }
+
+    #[test]
+    fn price_without_amount() {
+        assert_eq!(price("по запросу").amount(), None);
+        assert_eq!(price("").amount(), None);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(minutes("1,5 ч"), Some(90));
+        assert_eq!(minutes("1.5 часа"), Some(90));
+        assert_eq!(minutes("1 час 30 минут"), Some(90));
+        assert_eq!(minutes("60 минут"), Some(60));
+        assert_eq!(minutes("45"), Some(45));
+        assert_eq!(minutes("2 h"), Some(120));
+    }
+
+    #[test]
+    fn unparsable_durations() {
+        assert_eq!(minutes(""), None);
+        assert_eq!(minutes("по договорённости"), None);
+        assert_eq!(minutes("0 минут"), None);
+        assert_eq!(minutes("3 дня"), None);
+    }
+}
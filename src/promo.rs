@@ -0,0 +1,141 @@
+use crate::data::PromoCode;
+use crate::date::CalendarDate;
+use crate::url_query;
+use std::cell::RefCell;
+
+/// Параметры ссылки, в которых приходит код: `?ref=ANNA` или `?promo=SPRING`.
+const URL_PARAMS: [&str; 2] = ["ref", "promo"];
+const STORAGE_KEY: &str = "yainna.promo";
+
+thread_local! {
+    /// Код, найденный при загрузке страницы.
+    static ACTIVE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Код в каноническом виде для сравнения.
+pub fn normalize(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+pub fn find<'a>(codes: &'a [PromoCode], code: &str, today: CalendarDate) -> Option<&'a PromoCode> {
+    let code = normalize(code);
+    codes.iter().find(|promo| {
+        normalize(&promo.code) == code && promo.valid_until.is_none_or(|until| today <= until)
+    })
+}
+
+/// Действующий код из адреса страницы или сохранённый при прошлом визите.
+/// Код из адреса запоминается в localStorage, устаревший сохранённый — забывается.
+pub fn resolve(codes: &[PromoCode], today: CalendarDate) -> Option<PromoCode> {
+    let from_url = from_params(codes, &URL_PARAMS.map(url_query::get), today);
+    let promo = match from_url {
+        Some(promo) => {
+            store(Some(&promo.code));
+            Some(promo.clone())
+        }
+        None => {
+            let stored = storage().and_then(|storage| storage.get_item(STORAGE_KEY).ok()?);
+            let promo = stored.and_then(|code| find(codes, &code, today).cloned());
+            if promo.is_none() {
+                store(None);
+            }
+            promo
+        }
+    };
+
+    ACTIVE.with(|active| *active.borrow_mut() = promo.as_ref().map(|promo| promo.code.clone()));
+    promo
+}
+
+/// Первый действующий код среди параметров ссылки: опечатка в `ref` не мешает
+/// правильному коду в `promo`.
+fn from_params<'a>(
+    codes: &'a [PromoCode],
+    params: &[Option<String>],
+    today: CalendarDate,
+) -> Option<&'a PromoCode> {
+    params
+        .iter()
+        .flatten()
+        .find_map(|code| find(codes, code, today))
+}
+
+/// Действующий код; попадает в текст каждой заявки.
+pub fn active() -> Option<String> {
+    ACTIVE.with(|active| active.borrow().clone())
+}
+
+fn store(code: Option<&str>) {
+    let Some(storage) = storage() else {
+        return;
+    };
+    // Хранилище бывает недоступно (приватный режим) — тогда код действует до перезагрузки
+    let _ = match code {
+        Some(code) => storage.set_item(STORAGE_KEY, code),
+        None => storage.remove_item(STORAGE_KEY),
+    };
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+        CalendarDate::new(year, month, day).unwrap()
+    }
+
+    fn codes() -> Vec<PromoCode> {
+        vec![
+            PromoCode {
+                code: "Friend".to_string(),
+                banner: "По приглашению".to_string(),
+                valid_until: None,
+            },
+            PromoCode {
+                code: "SPRING".to_string(),
+                banner: "Весной".to_string(),
+                valid_until: Some(date(2025, 5, 31)),
+            },
+        ]
+    }
+
+    #[test]
+    fn normalizes_codes() {
+        assert_eq!(normalize("  friend "), "FRIEND");
+        assert_eq!(normalize("Весна"), "ВЕСНА");
+    }
+
+    #[test]
+    fn finds_codes_regardless_of_case_and_spaces() {
+        let codes = codes();
+        let today = date(2025, 1, 1);
+        assert_eq!(find(&codes, " friend", today), Some(&codes[0]));
+        assert_eq!(find(&codes, "spring", today), Some(&codes[1]));
+        assert_eq!(find(&codes, "SUMMER", today), None);
+        assert_eq!(find(&codes, "", today), None);
+    }
+
+    #[test]
+    fn takes_the_first_valid_code_from_the_link() {
+        let codes = codes();
+        let today = date(2025, 1, 1);
+        // ?ref=TYPO&promo=SPRING
+        let params = [Some("TYPO".to_string()), Some("SPRING".to_string())];
+        assert_eq!(from_params(&codes, &params, today), Some(&codes[1]));
+        let params = [Some("friend".to_string()), Some("SPRING".to_string())];
+        assert_eq!(from_params(&codes, &params, today), Some(&codes[0]));
+        assert_eq!(from_params(&codes, &[None, None], today), None);
+    }
+
+    #[test]
+    fn codes_expire_after_the_last_day() {
+        let codes = codes();
+        assert!(find(&codes, "SPRING", date(2025, 5, 31)).is_some());
+        assert!(find(&codes, "SPRING", date(2025, 6, 1)).is_none());
+        assert!(find(&codes, "FRIEND", date(2099, 1, 1)).is_some());
+    }
+}
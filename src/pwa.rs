@@ -0,0 +1,97 @@
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+const SERVICE_WORKER: &str = "/sw.js";
+const INSTALL_EVENT: &str = "beforeinstallprompt";
+const INSTALL_DISMISSED_KEY: &str = "yainna.install_dismissed";
+
+/// Регистрирует сервис-воркер для работы без сети.
+pub async fn register_service_worker() -> Result<(), String> {
+    let container = web_sys::window()
+        .ok_or("No window")?
+        .navigator()
+        .service_worker();
+    JsFuture::from(container.register(SERVICE_WORKER))
+        .await
+        .map_err(|e| format!("Failed to register service worker: {:?}", e))?;
+    Ok(())
+}
+
+/// Отложенное предложение установить сайт как приложение (Chrome, Android).
+#[derive(Clone)]
+pub struct InstallPrompt(web_sys::Event);
+
+impl InstallPrompt {
+    /// Показывает системный диалог; `true`, если посетитель согласился.
+    pub async fn show(&self) -> Result<bool, String> {
+        let event: &JsValue = self.0.as_ref();
+        let prompt = Reflect::get(event, &"prompt".into())
+            .ok()
+            .and_then(|prompt| prompt.dyn_into::<Function>().ok())
+            .ok_or("Install prompt is unavailable")?;
+        prompt
+            .call0(event)
+            .map_err(|e| format!("Failed to show install prompt: {:?}", e))?;
+
+        let choice = Reflect::get(event, &"userChoice".into())
+            .ok()
+            .and_then(|choice| choice.dyn_into::<Promise>().ok())
+            .ok_or("Install prompt has no userChoice")?;
+        let choice = JsFuture::from(choice)
+            .await
+            .map_err(|e| format!("Install prompt failed: {:?}", e))?;
+        let outcome = Reflect::get(&choice, &"outcome".into()).ok();
+        Ok(outcome.and_then(|outcome| outcome.as_string()).as_deref() == Some("accepted"))
+    }
+}
+
+/// Подписка на `beforeinstallprompt`; снимается при удалении.
+pub struct InstallListener {
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Drop for InstallListener {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            let _ = window.remove_event_listener_with_callback(
+                INSTALL_EVENT,
+                self.closure.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+/// Вызывает `on_prompt`, когда браузер готов предложить установку. Посетителям,
+/// которые уже отказались, предложение не показывается.
+pub fn listen_install_prompt(
+    on_prompt: impl Fn(InstallPrompt) + 'static,
+) -> Option<InstallListener> {
+    if install_dismissed() {
+        return None;
+    }
+    let closure = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+        // Вместо мини-панели браузера показываем свою кнопку
+        event.prevent_default();
+        on_prompt(InstallPrompt(event));
+    });
+    web_sys::window()?
+        .add_event_listener_with_callback(INSTALL_EVENT, closure.as_ref().unchecked_ref())
+        .ok()?;
+    Some(InstallListener { closure })
+}
+
+pub fn dismiss_install() {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(INSTALL_DISMISSED_KEY, "1");
+    }
+}
+
+fn install_dismissed() -> bool {
+    storage().is_some_and(|storage| matches!(storage.get_item(INSTALL_DISMISSED_KEY), Ok(Some(_))))
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
@@ -0,0 +1,40 @@
+use qrcode::{Color, QrCode};
+
+/// Пустая рамка вокруг кода в модулях: без неё сканеры плохо находят метки.
+const QUIET_ZONE: usize = 2;
+
+/// QR-код в виде одного SVG-пути. Одинаково вставляется в Yew-разметку
+/// и в HTML, который собирают консольные команды.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qr {
+    /// Сторона вместе с рамкой, для `viewBox="0 0 size size"`.
+    pub size: usize,
+    pub path: String,
+}
+
+impl Qr {
+    pub fn new(data: &str) -> Result<Self, String> {
+        let code = QrCode::new(data.as_bytes())
+            .map_err(|e| format!("Failed to build QR code for {}: {}", data, e))?;
+        let width = code.width();
+
+        let path = code
+            .to_colors()
+            .iter()
+            .enumerate()
+            .filter(|(_, color)| **color == Color::Dark)
+            .map(|(i, _)| {
+                format!(
+                    "M{} {}h1v1h-1z",
+                    i % width + QUIET_ZONE,
+                    i / width + QUIET_ZONE
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            size: width + QUIET_ZONE * 2,
+            path,
+        })
+    }
+}
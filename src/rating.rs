@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+const MIN_RATING: f32 = 1.0;
+const MAX_RATING: f32 = 5.0;
+
+/// Оценка от 1 до 5, допускаются дробные значения (например, 4.5).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(try_from = "f32")]
+pub struct Rating(f32);
+
+impl Rating {
+    pub fn value(self) -> f32 {
+        self.0
+    }
+
+    /// Звёзды для отображения: значение округляется до половины звезды.
+    pub fn stars(self) -> [Star; 5] {
+        let halves = (self.0 * 2.0).round() as u32;
+        std::array::from_fn(|i| {
+            let filled = halves.saturating_sub(i as u32 * 2);
+            match filled {
+                0 => Star::Empty,
+                1 => Star::Half,
+                _ => Star::Full,
+            }
+        })
+    }
+
+    /// Текст для экранных дикторов: «Оценка 4,5 из 5».
+    pub fn label(self) -> String {
+        format!("Оценка {} из 5", format_decimal(self.0))
+    }
+}
+
+impl TryFrom<f32> for Rating {
+    type Error = String;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        if (MIN_RATING..=MAX_RATING).contains(&value) {
+            Ok(Rating(value))
+        } else {
+            Err(format!(
+                "Rating must be between {} and {}, got: {}",
+                MIN_RATING, MAX_RATING, value
+            ))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Star {
+    Full,
+    Half,
+    Empty,
+}
+
+impl Star {
+    pub fn icon(self) -> &'static str {
+        match self {
+            Star::Full => "fas fa-star",
+            Star::Half => "fas fa-star-half-stroke",
+            Star::Empty => "far fa-star",
+        }
+    }
+}
+
+/// Сводка по оценкам: среднее, количество и распределение по звёздам.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatingSummary {
+    pub average: Rating,
+    pub count: usize,
+    /// Число оценок по звёздам, индекс 0 — одна звезда, индекс 4 — пять.
+    pub distribution: [usize; 5],
+}
+
+impl RatingSummary {
+    pub fn from_ratings(ratings: impl IntoIterator<Item = Rating>) -> Option<Self> {
+        let mut count = 0;
+        let mut sum = 0.0;
+        let mut distribution = [0; 5];
+
+        for rating in ratings {
+            count += 1;
+            sum += rating.value();
+            let bucket = (rating.value().round() as usize).clamp(1, 5) - 1;
+            distribution[bucket] += 1;
+        }
+
+        if count == 0 {
+            return None;
+        }
+
+        Some(Self {
+            average: Rating(sum / count as f32),
+            count,
+            distribution,
+        })
+    }
+}
+
+/// Число с одним знаком после запятой, без «,0» для целых.
+pub fn format_decimal(value: f32) -> String {
+    let rounded = (value * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as i32)
+    } else {
+        format!("{:.1}", rounded).replace('.', ",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating(value: f32) -> Rating {
+        Rating::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn accepts_only_one_to_five() {
+        assert!(Rating::try_from(0.9).is_err());
+        assert!(Rating::try_from(5.1).is_err());
+        assert!(Rating::try_from(f32::NAN).is_err());
+        assert_eq!(Rating::try_from(1.0).map(Rating::value), Ok(1.0));
+        assert_eq!(Rating::try_from(5.0).map(Rating::value), Ok(5.0));
+    }
+
+    #[test]
+    fn rounds_to_half_stars() {
+        use Star::{Empty, Full, Half};
+
+        assert_eq!(rating(4.25).stars(), [Full, Full, Full, Full, Half]);
+        assert_eq!(rating(4.75).stars(), [Full; 5]);
+        assert_eq!(rating(4.2).stars(), [Full, Full, Full, Full, Empty]);
+        assert_eq!(rating(1.0).stars(), [Full, Empty, Empty, Empty, Empty]);
+    }
+
+    #[test]
+    fn summary_of_no_ratings() {
+        assert_eq!(RatingSummary::from_ratings(Vec::new()), None);
+    }
+
+    #[test]
+    fn summary_buckets_by_nearest_star() {
+        let summary = RatingSummary::from_ratings([4.25, 4.75, 5.0, 1.0].map(rating)).unwrap();
+        assert_eq!(summary.count, 4);
+        assert_eq!(summary.distribution, [1, 0, 0, 1, 2]);
+        assert_eq!(summary.average.value(), 3.75);
+    }
+
+    #[test]
+    fn labels_use_decimal_comma() {
+        assert_eq!(rating(4.5).label(), "Оценка 4,5 из 5");
+        assert_eq!(rating(5.0).label(), "Оценка 5 из 5");
+        assert_eq!(format_decimal(4.25), "4,3");
+    }
+}
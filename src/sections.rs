@@ -0,0 +1,10 @@
+pub mod calculator;
+pub mod consent_checkbox;
+pub mod directions;
+pub mod faq;
+pub mod gift_certificates;
+pub mod legal_page;
+pub mod map_embed;
+pub mod service_details;
+pub mod testimonial_card;
+pub mod testimonial_carousel;
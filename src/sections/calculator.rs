@@ -0,0 +1,132 @@
+use crate::analytics;
+use crate::booking;
+use crate::data;
+use crate::ics;
+use crate::pricing;
+use crate::text;
+use crate::url_query;
+use crate::{BookingLink, on_text_input};
+use yew::prelude::*;
+
+/// Итог калькулятора внизу экрана: сумма с учётом комплексов, время, заявка в WhatsApp
+/// и файл календаря для выбранного времени записи.
+pub fn render_calculator_summary(
+    services: &data::ServicesSection,
+    selected: &[String],
+    info: &data::SalonInfo,
+    contacts: &data::Contacts,
+    slot: &UseStateHandle<String>,
+    on_clear: Callback<MouseEvent>,
+) -> Html {
+    let items = services
+        .items()
+        .filter(|item| selected.contains(&item.id))
+        .collect::<Vec<_>>();
+    if items.is_empty() {
+        return html! {
+            <div class="fixed bottom-0 inset-x-0 z-40 bg-white/95 shadow-2xl border-t border-amber-200 py-4 px-4 text-center text-amber-700 font-body">
+                { "Отметьте услуги, чтобы узнать стоимость и время" }
+            </div>
+        };
+    }
+
+    let quote = pricing::Quote::new(&items, &services.combos);
+    let message = booking::BookingMessage::with_services(&items, &quote);
+    let prefix = if quote.approximate { "≈ " } else { "" };
+
+    let calendar_url = ics::LocalDateTime::parse(slot).ok().map(|start| {
+        let names = items
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect::<Vec<_>>();
+        let appointment = ics::Appointment {
+            title: format!("{}: {}", info.name, names.join(", ")),
+            description: format!("{}\nТелефон: {}", names.join("\n"), contacts.phone),
+            location: contacts.address.clone(),
+            start,
+            minutes: if quote.minutes > 0 {
+                quote.minutes
+            } else {
+                ics::DEFAULT_MINUTES
+            },
+        };
+        url_query::data_url("text/calendar", &appointment.to_ics(&ics::utc_stamp()))
+    });
+
+    html! {
+        <div class="fixed bottom-0 inset-x-0 z-40 bg-white/95 shadow-2xl border-t border-amber-200 py-4 px-4 font-body">
+            <div class="max-w-7xl mx-auto flex flex-col md:flex-row md:items-center gap-4">
+                <div class="flex-1 text-amber-800">
+                    <p class="text-sm">
+                        { format!(
+                            "{} {} · {}{}",
+                            items.len(),
+                            text::plural_ru(items.len(), "услуга", "услуги", "услуг"),
+                            prefix,
+                            pricing::format_minutes(quote.minutes)
+                        ) }
+                    </p>
+                    { for quote.combos.iter().map(|combo| html! {
+                        <p class="text-sm text-green-700">
+                            <i class="fas fa-gift mr-1" aria-hidden="true"></i>
+                            { format!("Комплекс «{}»: −{}", combo.name, pricing::format_rub(combo.saving)) }
+                        </p>
+                    }) }
+                </div>
+                <div class="flex items-baseline gap-3">
+                    { if quote.saving() > 0 {
+                        html! {
+                            <span class="text-amber-500 line-through">{ pricing::format_rub(quote.subtotal) }</span>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                    <span class="text-2xl font-heading font-bold text-amber-900">
+                        { format!("{}{}", prefix, pricing::format_rub(quote.total)) }
+                    </span>
+                </div>
+                <div class="flex gap-2">
+                    <BookingLink
+                        href={contacts.whatsapp.clone()}
+                        message={message}
+                        target="_blank"
+                        onclick={analytics::on_click(analytics::Goal::Messenger { origin: "calculator" })}
+                        class="bg-amber-600 text-white px-6 py-3 rounded-full font-semibold hover:bg-amber-700 transition-colors"
+                    >
+                        <i class="fab fa-whatsapp mr-2" aria-hidden="true"></i>
+                        { "Записаться" }
+                    </BookingLink>
+                    <button
+                        onclick={on_clear}
+                        aria-label="Сбросить выбор"
+                        class="w-12 h-12 rounded-full bg-amber-100 text-amber-700 hover:bg-amber-200 transition-colors"
+                    >
+                        <i class="fas fa-times" aria-hidden="true"></i>
+                    </button>
+                </div>
+            </div>
+            <div class="max-w-7xl mx-auto mt-3 flex flex-wrap items-center gap-2 text-sm text-amber-700">
+                <label class="flex items-center gap-2">
+                    <i class="far fa-calendar-plus" aria-hidden="true"></i>
+                    <span>{ "Записались? Добавьте визит в календарь:" }</span>
+                    <input
+                        type="datetime-local"
+                        value={(**slot).clone()}
+                        oninput={on_text_input(slot)}
+                        class="px-3 py-1 rounded-full border-2 border-amber-200 focus:border-amber-500 focus:outline-none focus:ring-4 focus:ring-amber-200 text-amber-900"
+                    />
+                </label>
+                { for calendar_url.map(|href| html! {
+                    <a
+                        href={href}
+                        download="zapis.ics"
+                        class="px-4 py-1 rounded-full bg-amber-100 text-amber-800 font-semibold hover:bg-amber-200 transition-colors"
+                    >
+                        <i class="fas fa-download mr-1" aria-hidden="true"></i>
+                        { "Скачать .ics" }
+                    </a>
+                }) }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,68 @@
+use crate::data;
+use crate::legal;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ConsentCheckboxProps {
+    pub documents: Vec<data::LegalDocument>,
+    pub checked: bool,
+    /// Форму пытались отправить без согласия.
+    #[prop_or_default]
+    pub error: bool,
+    pub on_change: Callback<bool>,
+}
+
+/// Согласие на обработку персональных данных со ссылками на документы.
+/// Без документов с `form_consent` галочка не показывается.
+#[function_component(ConsentCheckbox)]
+pub fn consent_checkbox(props: &ConsentCheckboxProps) -> Html {
+    let documents = legal::consent_documents(&props.documents);
+    if documents.is_empty() {
+        return html! {};
+    }
+
+    let onchange = {
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                on_change.emit(input.checked());
+            }
+        })
+    };
+
+    html! {
+        <div>
+            <label class="flex items-start gap-3 font-body text-sm text-amber-800">
+                <input
+                    type="checkbox"
+                    aria-required="true"
+                    aria-invalid={props.error.to_string()}
+                    aria-describedby={props.error.then_some("consent-error")}
+                    checked={props.checked}
+                    onchange={onchange}
+                    class="mt-1 w-4 h-4 accent-amber-600 flex-shrink-0"
+                />
+                <span>
+                    { "Я даю согласие на обработку персональных данных и принимаю условия: " }
+                    { for documents.iter().enumerate().map(|(i, document)| html! {
+                        <>
+                            { if i > 0 { ", " } else { "" } }
+                            <a href={legal::page_url(document)} target="_blank" class="underline underline-offset-2 hover:text-amber-600">
+                                { &document.title }
+                            </a>
+                        </>
+                    }) }
+                </span>
+            </label>
+            { if props.error {
+                html! {
+                    <p id="consent-error" class="mt-2 text-sm text-red-600 font-body" role="alert">
+                        { "Без согласия на обработку данных отправить заявку нельзя" }
+                    </p>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,188 @@
+use crate::data;
+use crate::markdown;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct DirectionsGuideProps {
+    pub directions: data::Directions,
+}
+
+/// Маршрут до двери студии по шагам: фото с подписью, листается свайпом,
+/// стрелками клавиатуры или номерами шагов.
+#[function_component(DirectionsGuide)]
+pub fn directions_guide(props: &DirectionsGuideProps) -> Html {
+    let directions = &props.directions;
+    let current = use_state(|| 0usize);
+    let touch_start = use_mut_ref(|| None::<i32>);
+
+    let count = directions.steps.len();
+    let index = (*current).min(count.saturating_sub(1));
+
+    // Маршрут не зацикливается: с последнего шага дальше идти некуда
+    let show = {
+        let current = current.clone();
+        move |delta: isize| {
+            let next = (index as isize + delta).clamp(0, count as isize - 1) as usize;
+            current.set(next);
+        }
+    };
+
+    let onkeydown = {
+        let show = show.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowLeft" => {
+                e.prevent_default();
+                show(-1);
+            }
+            "ArrowRight" => {
+                e.prevent_default();
+                show(1);
+            }
+            _ => {}
+        })
+    };
+    let ontouchstart = {
+        let touch_start = touch_start.clone();
+        Callback::from(move |e: TouchEvent| {
+            *touch_start.borrow_mut() = e.touches().get(0).map(|t| t.client_x());
+        })
+    };
+    let ontouchend = {
+        let show = show.clone();
+        Callback::from(move |e: TouchEvent| {
+            let start = touch_start.borrow_mut().take();
+            let end = e.changed_touches().get(0).map(|t| t.client_x());
+            if let (Some(start), Some(end)) = (start, end) {
+                if end - start > 40 {
+                    show(-1);
+                } else if start - end > 40 {
+                    show(1);
+                }
+            }
+        })
+    };
+    let onprev = {
+        let show = show.clone();
+        Callback::from(move |_: MouseEvent| show(-1))
+    };
+    let onnext = Callback::from(move |_: MouseEvent| show(1));
+
+    let notes = [
+        ("fa-parking", "Парковка", &directions.parking),
+        ("fa-bus", "Общественный транспорт", &directions.transport),
+    ];
+
+    html! {
+        <div class="mt-12 bg-gradient-to-br from-amber-50 to-orange-100 rounded-2xl p-6 md:p-8 shadow-lg">
+            <h3 class="text-2xl font-heading font-bold text-amber-900 mb-6">
+                <i class="fas fa-shoe-prints mr-3 text-amber-600" aria-hidden="true"></i>
+                { &directions.title }
+            </h3>
+
+            <div class="grid lg:grid-cols-3 gap-8">
+                <div
+                    class="lg:col-span-2 focus:outline-none focus-visible:ring-4 focus-visible:ring-amber-300 rounded-2xl"
+                    tabindex="0"
+                    role="region"
+                    aria-roledescription="карусель"
+                    aria-label={directions.title.clone()}
+                    onkeydown={onkeydown}
+                    ontouchstart={ontouchstart}
+                    ontouchend={ontouchend}
+                >
+                    <div class="overflow-hidden rounded-xl bg-white shadow">
+                        <div
+                            class="flex transition-transform motion-reduce:transition-none duration-500 ease-out"
+                            style={format!("transform: translateX(-{}%);", index * 100)}
+                        >
+                            { for directions.steps.iter().enumerate().map(|(i, step)| html! {
+                                <figure
+                                    class="w-full flex-shrink-0"
+                                    aria-hidden={(i != index).to_string()}
+                                    inert={(i != index).then_some("")}
+                                    aria-roledescription="шаг"
+                                >
+                                    { for step.photo.clone().map(|photo| html! {
+                                        <img
+                                            src={photo}
+                                            alt={step.alt.clone().unwrap_or_else(|| format!("Шаг {} из {}", i + 1, count))}
+                                            loading="lazy"
+                                            class="w-full aspect-[3/2] object-cover"
+                                        />
+                                    }) }
+                                    <figcaption class="flex items-start gap-3 p-4 font-body text-amber-900">
+                                        <span class="w-8 h-8 rounded-full bg-amber-600 text-white font-heading font-bold flex items-center justify-center flex-shrink-0">
+                                            { i + 1 }
+                                        </span>
+                                        <div class="space-y-1">
+                                            { markdown::render(&step.caption) }
+                                        </div>
+                                    </figcaption>
+                                </figure>
+                            }) }
+                        </div>
+                    </div>
+
+                    { if count > 1 {
+                        html! {
+                            <div class="flex items-center justify-center gap-4 mt-4">
+                                <button
+                                    onclick={onprev}
+                                    disabled={index == 0}
+                                    aria-label="Предыдущий шаг"
+                                    class="w-10 h-10 rounded-full bg-white text-amber-700 shadow hover:bg-amber-100 transition-colors disabled:opacity-40 disabled:cursor-default"
+                                >
+                                    <i class="fas fa-chevron-left" aria-hidden="true"></i>
+                                </button>
+                                <div class="flex gap-2">
+                                    { for (0..count).map(|i| {
+                                        let current = current.clone();
+                                        html! {
+                                            <button
+                                                onclick={Callback::from(move |_| current.set(i))}
+                                                aria-label={format!("Шаг {} из {}", i + 1, count)}
+                                                aria-current={(i == index).to_string()}
+                                                class={format!(
+                                                    "w-8 h-8 rounded-full text-sm font-heading font-bold transition-colors {}",
+                                                    if i == index { "bg-amber-600 text-white" } else { "bg-white text-amber-700 hover:bg-amber-200" }
+                                                )}
+                                            >
+                                                { i + 1 }
+                                            </button>
+                                        }
+                                    }) }
+                                </div>
+                                <button
+                                    onclick={onnext}
+                                    disabled={index + 1 == count}
+                                    aria-label="Следующий шаг"
+                                    class="w-10 h-10 rounded-full bg-white text-amber-700 shadow hover:bg-amber-100 transition-colors disabled:opacity-40 disabled:cursor-default"
+                                >
+                                    <i class="fas fa-chevron-right" aria-hidden="true"></i>
+                                </button>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                </div>
+
+                <div class="space-y-4">
+                    { for notes.into_iter().filter_map(|(icon, title, text)| {
+                        text.as_ref().map(|text| html! {
+                            <div class="bg-white rounded-xl p-5 shadow">
+                                <p class="flex items-center text-lg font-heading font-semibold text-amber-900 mb-2">
+                                    <i class={format!("fas {} mr-3 text-amber-600", icon)} aria-hidden="true"></i>
+                                    { title }
+                                </p>
+                                <div class="text-amber-800 font-body space-y-1">
+                                    { markdown::render(text) }
+                                </div>
+                            </div>
+                        })
+                    }) }
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,178 @@
+use crate::BookingLink;
+use crate::analytics;
+use crate::data;
+use crate::markdown;
+use crate::url_query;
+use std::collections::HashSet;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct FaqSectionProps {
+    pub faq: data::FaqSection,
+    pub contacts: data::Contacts,
+}
+
+#[function_component(FaqSection)]
+pub fn faq_section(props: &FaqSectionProps) -> Html {
+    let faq = &props.faq;
+    let query = use_state(String::new);
+    // Вопрос из ссылки вида #faq-... открыт сразу
+    let open = use_state(|| {
+        url_query::hash()
+            .filter(|hash| hash.starts_with(data::FAQ_ANCHOR_PREFIX))
+            .into_iter()
+            .collect::<HashSet<_>>()
+    });
+
+    // Контент грузится асинхронно, поэтому браузер сам до якоря не прокручивает
+    use_effect_with((), |_| {
+        let target = url_query::hash()
+            .filter(|hash| hash.starts_with(data::FAQ_ANCHOR_PREFIX))
+            .and_then(|hash| web_sys::window()?.document()?.get_element_by_id(&hash));
+        if let Some(element) = target {
+            element.scroll_into_view();
+        }
+        || ()
+    });
+
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                query.set(input.value());
+            }
+        })
+    };
+
+    let on_toggle = {
+        let open = open.clone();
+        Callback::from(move |anchor: String| {
+            let mut next = (*open).clone();
+            if next.remove(&anchor) {
+                if url_query::hash().as_deref() == Some(anchor.as_str()) {
+                    url_query::set_hash(None);
+                }
+            } else {
+                url_query::set_hash(Some(&anchor));
+                next.insert(anchor);
+            }
+            open.set(next);
+        })
+    };
+
+    let groups = faq
+        .groups
+        .iter()
+        .map(|group| {
+            let items = group
+                .items
+                .iter()
+                .filter(|item| item.matches(&query))
+                .collect::<Vec<_>>();
+            (group, items)
+        })
+        .filter(|(_, items)| !items.is_empty())
+        .collect::<Vec<_>>();
+
+    html! {
+        <section id="faq" class="py-20 px-4 bg-gradient-to-br from-amber-50 via-yellow-50 to-orange-100" aria-labelledby="faq-title">
+            <script type="application/ld+json">{ faq.json_ld().to_string() }</script>
+            <div class="max-w-3xl mx-auto">
+                <div class="text-center mb-10">
+                    <h2 id="faq-title" class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
+                        { &faq.title }
+                    </h2>
+                    <p class="text-lg text-amber-700 font-body">
+                        { &faq.subtitle }
+                    </p>
+                </div>
+
+                <div class="mb-8 relative">
+                    <i class="fas fa-search absolute left-4 top-1/2 -translate-y-1/2 text-amber-400" aria-hidden="true"></i>
+                    <input
+                        type="search"
+                        value={(*query).clone()}
+                        oninput={oninput}
+                        placeholder="Поиск по вопросам"
+                        aria-label="Поиск по вопросам"
+                        class="w-full pl-11 pr-4 py-2 rounded-full border-2 border-amber-200 focus:border-amber-500 focus:outline-none focus:ring-4 focus:ring-amber-200 font-body text-amber-900 bg-white"
+                    />
+                </div>
+
+                { if groups.is_empty() {
+                    html! {
+                        <p class="text-center text-amber-700 font-body">
+                            { "Ответа пока нет — " }
+                            <BookingLink href={props.contacts.whatsapp.clone()} target="_blank" onclick={analytics::on_click(analytics::Goal::Messenger { origin: "faq" })} class="underline underline-offset-2">
+                                { "задайте вопрос в WhatsApp" }
+                            </BookingLink>
+                        </p>
+                    }
+                } else {
+                    html! {}
+                }}
+
+                <div class="space-y-10">
+                    { for groups.iter().map(|(group, items)| html! {
+                        <div>
+                            <h3 class="text-2xl font-heading font-bold text-amber-900 mb-4">
+                                { for group.icon.iter().map(|icon| html! {
+                                    <i class={format!("fas {} mr-3 text-amber-600", icon)} aria-hidden="true"></i>
+                                }) }
+                                { &group.name }
+                            </h3>
+                            <div class="space-y-3">
+                                { for items.iter().map(|item| {
+                                    let anchor = item.anchor();
+                                    let is_open = open.contains(&anchor);
+                                    let button_id = format!("{}-question", anchor);
+                                    let panel_id = format!("{}-answer", anchor);
+                                    let onclick = {
+                                        let anchor = anchor.clone();
+                                        on_toggle.reform(move |_| anchor.clone())
+                                    };
+
+                                    html! {
+                                        <div id={anchor.clone()} class="bg-white rounded-xl shadow scroll-mt-8">
+                                            <h4>
+                                                <button
+                                                    id={button_id.clone()}
+                                                    onclick={onclick}
+                                                    aria-expanded={is_open.to_string()}
+                                                    aria-controls={panel_id.clone()}
+                                                    class="w-full flex justify-between items-center gap-4 text-left px-5 py-4 font-heading font-semibold text-amber-900 hover:text-amber-700"
+                                                >
+                                                    <span>{ &item.question }</span>
+                                                    <i class={format!(
+                                                        "fas fa-chevron-down text-amber-500 transition-transform motion-reduce:transition-none duration-300 {}",
+                                                        if is_open { "rotate-180" } else { "" }
+                                                    )} aria-hidden="true"></i>
+                                                </button>
+                                            </h4>
+                                            <div
+                                                id={panel_id}
+                                                role="region"
+                                                aria-labelledby={button_id}
+                                                hidden={!is_open}
+                                                class="px-5 pb-4 text-amber-700 font-body space-y-2"
+                                            >
+                                                { markdown::render(&item.answer) }
+                                                <a
+                                                    href={format!("#{}", anchor)}
+                                                    class="inline-block text-xs text-amber-500 hover:text-amber-700"
+                                                >
+                                                    <i class="fas fa-link mr-1" aria-hidden="true"></i>
+                                                    { "Ссылка на вопрос" }
+                                                </a>
+                                            </div>
+                                        </div>
+                                    }
+                                }) }
+                            </div>
+                        </div>
+                    }) }
+                </div>
+            </div>
+        </section>
+    }
+}
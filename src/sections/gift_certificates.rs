@@ -0,0 +1,341 @@
+use crate::analytics;
+use crate::booking;
+use crate::content_loader;
+use crate::data;
+use crate::date;
+use crate::gift_certificate;
+use crate::legal;
+use crate::markdown;
+use crate::on_text_input;
+use crate::pricing;
+use crate::sections::consent_checkbox::ConsentCheckbox;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct GiftCertificatesSectionProps {
+    pub certificates: data::GiftCertificatesSection,
+    pub services: data::ServicesSection,
+    pub info: data::SalonInfo,
+    pub contacts: data::Contacts,
+    pub legal: Vec<data::LegalDocument>,
+}
+
+#[function_component(GiftCertificatesSection)]
+pub fn gift_certificates_section(props: &GiftCertificatesSectionProps) -> Html {
+    use gift_certificate::{CertificateStatus, CertificateValue};
+
+    let certificates = &props.certificates;
+    let value = use_state(|| {
+        certificates
+            .amounts
+            .first()
+            .map(|amount| CertificateValue::Amount(*amount))
+    });
+    let recipient = use_state(String::new);
+    let sender = use_state(String::new);
+    let wish = use_state(String::new);
+    // Код создаётся один раз: повторная отправка заявки не плодит новые коды
+    let code = use_state(|| None::<String>);
+    let check_code = use_state(String::new);
+    let check_result = use_state(|| None::<Result<CertificateStatus, String>>);
+    let agreed = use_state(|| false);
+    let consent_error = use_state(|| false);
+
+    let value_title = (*value).as_ref().map(|value| value.title(&props.services));
+    let valid_until = date::CalendarDate::today().add_months(certificates.valid_months);
+
+    let onsubmit = {
+        let value_title = value_title.clone();
+        let recipient = recipient.clone();
+        let sender = sender.clone();
+        let wish = wish.clone();
+        let code = code.clone();
+        let prefix = certificates.code_prefix.clone();
+        let whatsapp = props.contacts.whatsapp.clone();
+        let legal = props.legal.clone();
+        let agreed = agreed.clone();
+        let consent_error = consent_error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if !legal::may_submit(&legal, *agreed) {
+                consent_error.set(true);
+                return;
+            }
+            let Some(value_title) = value_title.clone() else {
+                return;
+            };
+            let current = match (*code).clone() {
+                Some(current) => current,
+                None => match gift_certificate::generate_code(&prefix) {
+                    Ok(generated) => {
+                        code.set(Some(generated.clone()));
+                        generated
+                    }
+                    Err(e) => {
+                        gloo_console::error!("Failed to generate certificate code:", &e);
+                        return;
+                    }
+                },
+            };
+
+            let mut message = booking::BookingMessage::new(
+                "Здравствуйте! Хочу приобрести подарочный сертификат.",
+            );
+            message.push(format!("Сертификат: {}", value_title));
+            for (label, text) in [
+                ("Для", &*recipient),
+                ("От", &*sender),
+                ("Пожелание", &*wish),
+            ] {
+                if !text.trim().is_empty() {
+                    message.push(format!("{}: {}", label, text.trim()));
+                }
+            }
+            message.push(format!("Код: {}", current));
+            message.push(format!("Действует до: {}", valid_until));
+
+            analytics::reach_goal(&analytics::Goal::Messenger {
+                origin: "gift_certificate",
+            });
+            if let Some(window) = web_sys::window() {
+                let _ = window.open_with_url_and_target(&message.whatsapp_url(&whatsapp), "_blank");
+            }
+        })
+    };
+
+    let oncheck = {
+        let check_code = check_code.clone();
+        let check_result = check_result.clone();
+        let codes = certificates.codes.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let (Some(codes), false) = (codes.clone(), check_code.trim().is_empty()) else {
+                return;
+            };
+            let code = (*check_code).clone();
+            let check_result = check_result.clone();
+            spawn_local(async move {
+                let result = content_loader::fetch_data::<data::CertificateRegistry>(&codes)
+                    .await
+                    .map(|registry| registry.check(&code, date::CalendarDate::today()));
+                check_result.set(Some(result));
+            });
+        })
+    };
+
+    let choice_button = |choice: CertificateValue, label: String| {
+        let is_active = value.as_ref() == Some(&choice);
+        let onclick = {
+            let value = value.clone();
+            Callback::from(move |_| value.set(Some(choice.clone())))
+        };
+        html! {
+            <button
+                type="button"
+                onclick={onclick}
+                aria-pressed={is_active.to_string()}
+                class={format!(
+                    "px-4 py-2 rounded-full font-body font-semibold transition-all motion-reduce:transition-none duration-300 {}",
+                    if is_active {
+                        "bg-amber-600 text-white shadow-lg"
+                    } else {
+                        "bg-amber-100 text-amber-700 hover:bg-amber-200"
+                    }
+                )}
+            >
+                { label }
+            </button>
+        }
+    };
+
+    let input_class = "w-full px-4 py-2 rounded-xl border-2 border-amber-200 focus:border-amber-500 focus:outline-none focus:ring-4 focus:ring-amber-200 font-body text-amber-900";
+    let card_style = certificates.background.as_ref().map(|background| {
+        format!(
+            "background-image: url('{}'); background-size: cover;",
+            background
+        )
+    });
+
+    html! {
+        <section id="gift-certificates" class="py-20 px-4 bg-white" aria-labelledby="gift-certificates-title">
+            <div class="max-w-6xl mx-auto">
+                <div class="text-center mb-12">
+                    <h2 id="gift-certificates-title" class="text-4xl md:text-5xl font-heading font-extrabold text-amber-900 mb-3">
+                        { &certificates.title }
+                    </h2>
+                    <p class="text-lg text-amber-700 font-body mb-2">
+                        { &certificates.subtitle }
+                    </p>
+                    <div class="text-md text-amber-600 font-body space-y-2">
+                        { markdown::render(&certificates.description) }
+                    </div>
+                </div>
+
+                <div class="grid md:grid-cols-2 gap-10 items-start">
+                    <form onsubmit={onsubmit} class="space-y-5">
+                        { if certificates.amounts.is_empty() {
+                            html! {}
+                        } else {
+                            html! {
+                                <fieldset>
+                                    <legend class="font-heading font-semibold text-amber-900 mb-2">{ "Номинал" }</legend>
+                                    <div class="flex flex-wrap gap-2">
+                                        { for certificates.amounts.iter().map(|amount| {
+                                            choice_button(CertificateValue::Amount(*amount), pricing::format_rub(*amount))
+                                        }) }
+                                    </div>
+                                </fieldset>
+                            }
+                        }}
+                        { if certificates.service_ids.is_empty() {
+                            html! {}
+                        } else {
+                            html! {
+                                <fieldset>
+                                    <legend class="font-heading font-semibold text-amber-900 mb-2">{ "Или услуга" }</legend>
+                                    <div class="flex flex-wrap gap-2">
+                                        { for certificates.service_ids.iter().filter_map(|id| props.services.find(id)).map(|item| {
+                                            choice_button(CertificateValue::Service(item.id.clone()), item.name.clone())
+                                        }) }
+                                    </div>
+                                </fieldset>
+                            }
+                        }}
+                        <label class="block">
+                            <span class="font-heading font-semibold text-amber-900">{ "Кому" }</span>
+                            <input type="text" value={(*recipient).clone()} oninput={on_text_input(&recipient)} placeholder="Имя получателя" class={input_class} />
+                        </label>
+                        <label class="block">
+                            <span class="font-heading font-semibold text-amber-900">{ "От кого" }</span>
+                            <input type="text" value={(*sender).clone()} oninput={on_text_input(&sender)} placeholder="Ваше имя" class={input_class} />
+                        </label>
+                        <label class="block">
+                            <span class="font-heading font-semibold text-amber-900">{ "Пожелание" }</span>
+                            <textarea value={(*wish).clone()} oninput={on_text_input(&wish)} rows="3" class={input_class}></textarea>
+                        </label>
+                        <ConsentCheckbox
+                            documents={props.legal.clone()}
+                            checked={*agreed}
+                            error={*consent_error && !*agreed}
+                            on_change={{
+                                let agreed = agreed.clone();
+                                Callback::from(move |checked| agreed.set(checked))
+                            }}
+                        />
+                        <button
+                            type="submit"
+                            disabled={value.is_none()}
+                            class="w-full bg-amber-600 text-white py-3 rounded-full font-body font-semibold hover:bg-amber-700 transition-colors disabled:opacity-50"
+                        >
+                            <i class="fab fa-whatsapp mr-2" aria-hidden="true"></i>
+                            { "Оформить через WhatsApp" }
+                        </button>
+                    </form>
+
+                    // Превью сертификата
+                    <div
+                        class="relative aspect-[3/2] rounded-3xl shadow-2xl overflow-hidden bg-gradient-to-br from-amber-500 via-orange-400 to-amber-700 text-white p-8 flex flex-col justify-between"
+                        style={card_style}
+                    >
+                        <div class="flex justify-between items-start">
+                            <div>
+                                <p class="text-sm uppercase tracking-widest text-white/80 font-body">{ "Подарочный сертификат" }</p>
+                                <p class="text-3xl font-heading font-extrabold">{ &props.info.name }</p>
+                            </div>
+                            <i class="fas fa-gift text-4xl text-white/80" aria-hidden="true"></i>
+                        </div>
+                        <div>
+                            <p class="text-3xl md:text-4xl font-heading font-bold mb-2">
+                                { value_title.clone().unwrap_or_default() }
+                            </p>
+                            { if recipient.trim().is_empty() {
+                                html! {}
+                            } else {
+                                html! { <p class="font-body">{ format!("Для: {}", recipient.trim()) }</p> }
+                            }}
+                            { if wish.trim().is_empty() {
+                                html! {}
+                            } else {
+                                html! { <p class="font-body italic text-white/90 line-clamp-2">{ wish.trim() }</p> }
+                            }}
+                        </div>
+                        <div class="flex justify-between items-end text-sm font-body text-white/90">
+                            <span class="font-mono tracking-wider">
+                                { (*code).clone().unwrap_or_else(|| "Код появится после оформления".to_string()) }
+                            </span>
+                            <span>
+                                { format!("Действует до {}", valid_until) }
+                            </span>
+                        </div>
+                    </div>
+                </div>
+
+                { for certificates.codes.iter().map(|_| html! {
+                    <form onsubmit={oncheck.clone()} class="max-w-xl mx-auto mt-16 text-center">
+                        <h3 class="text-2xl font-heading font-bold text-amber-900 mb-4">{ "Проверить сертификат" }</h3>
+                        <div class="flex gap-2">
+                            <input
+                                type="text"
+                                value={(*check_code).clone()}
+                                oninput={on_text_input(&check_code)}
+                                placeholder={format!("{}-XXXX-XXXX-XXXX-XXXX", certificates.code_prefix)}
+                                aria-label="Код сертификата"
+                                class={format!("{} uppercase font-mono", input_class)}
+                            />
+                            <button type="submit" class="bg-amber-600 text-white px-6 rounded-full font-body font-semibold hover:bg-amber-700 transition-colors">
+                                { "Проверить" }
+                            </button>
+                        </div>
+                        { render_certificate_status((*check_result).as_ref()) }
+                    </form>
+                }) }
+            </div>
+        </section>
+    }
+}
+
+fn render_certificate_status(
+    result: Option<&Result<gift_certificate::CertificateStatus, String>>,
+) -> Html {
+    use gift_certificate::CertificateStatus;
+
+    let (icon, class, text) = match result {
+        None => return html! {},
+        Some(Err(_)) => (
+            "fa-exclamation-circle",
+            "text-red-600",
+            "Не удалось проверить код, попробуйте позже".to_string(),
+        ),
+        Some(Ok(CertificateStatus::Valid(certificate))) => (
+            "fa-check-circle",
+            "text-green-700",
+            format!(
+                "Сертификат действителен: {}, до {}",
+                certificate.title, certificate.valid_until
+            ),
+        ),
+        Some(Ok(CertificateStatus::Expired(certificate))) => (
+            "fa-hourglass-end",
+            "text-amber-700",
+            format!("Срок действия истёк {}", certificate.valid_until),
+        ),
+        Some(Ok(CertificateStatus::Redeemed(_))) => (
+            "fa-times-circle",
+            "text-amber-700",
+            "Сертификат уже использован".to_string(),
+        ),
+        Some(Ok(CertificateStatus::NotFound)) => (
+            "fa-question-circle",
+            "text-red-600",
+            "Сертификат с таким кодом не найден".to_string(),
+        ),
+    };
+
+    html! {
+        <p class={format!("mt-4 font-body {}", class)} role="status">
+            <i class={format!("fas {} mr-2", icon)} aria-hidden="true"></i>
+            { text }
+        </p>
+    }
+}
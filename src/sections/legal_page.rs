@@ -0,0 +1,63 @@
+use crate::content_loader;
+use crate::data;
+use crate::legal;
+use crate::markdown;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct LegalPageProps {
+    pub document: data::LegalDocument,
+    pub info: data::SalonInfo,
+    pub contacts: data::Contacts,
+}
+
+#[function_component(LegalPage)]
+pub fn legal_page(props: &LegalPageProps) -> Html {
+    let text = use_state(|| None::<Result<String, String>>);
+
+    {
+        let text = text.clone();
+        use_effect_with(props.document.file.clone(), move |file| {
+            let file = file.clone();
+            spawn_local(async move {
+                let result = content_loader::fetch_text(&file).await;
+                if let Err(e) = &result {
+                    gloo_console::error!("Failed to load legal document:", e);
+                }
+                text.set(Some(result));
+            });
+            || ()
+        });
+    }
+
+    let body = match (*text).as_ref() {
+        None => html! { <p class="text-amber-700">{ "Загрузка..." }</p> },
+        Some(Ok(text)) => match legal::fill(text, &props.info, &props.contacts) {
+            Ok(text) => markdown::render(&text),
+            Err(_) => html! {
+                <p class="text-amber-700">{ "Документ готовится и скоро появится" }</p>
+            },
+        },
+        Some(Err(_)) => html! {
+            <p class="text-red-600">{ "Не удалось загрузить документ, попробуйте обновить страницу" }</p>
+        },
+    };
+
+    html! {
+        <main class="min-h-screen bg-white py-12 px-4">
+            <article class="max-w-3xl mx-auto">
+                <a href="/" class="inline-flex items-center text-amber-700 hover:text-amber-900 font-body mb-8">
+                    <i class="fas fa-arrow-left mr-2" aria-hidden="true"></i>
+                    { &props.info.name }
+                </a>
+                <h1 class="text-3xl md:text-4xl font-heading font-extrabold text-amber-900 mb-8">
+                    { &props.document.title }
+                </h1>
+                <div class="text-amber-900 font-body leading-relaxed space-y-4">
+                    { body }
+                </div>
+            </article>
+        </main>
+    }
+}
@@ -0,0 +1,94 @@
+use crate::data;
+use crate::viewport;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct MapEmbedProps {
+    pub location: data::Location,
+}
+
+/// Карта Яндекса подгружается по клику или когда блок подъезжает к экрану.
+/// До загрузки и при заблокированном iframe видна картинка карты или адрес.
+#[function_component(MapEmbed)]
+pub fn map_embed(props: &MapEmbedProps) -> Html {
+    let location = &props.location;
+    let requested = use_state(|| false);
+    let loaded = use_state(|| false);
+    let container = use_node_ref();
+
+    {
+        let requested = requested.clone();
+        let container = container.clone();
+        use_effect_with((), move |_| {
+            let observer = container.cast::<web_sys::Element>().and_then(|element| {
+                viewport::observe_once(&element, "200px", move || requested.set(true))
+            });
+            move || drop(observer)
+        });
+    }
+
+    let onrequest = {
+        let requested = requested.clone();
+        Callback::from(move |_: MouseEvent| requested.set(true))
+    };
+    let onload = {
+        let loaded = loaded.clone();
+        Callback::from(move |_: Event| loaded.set(true))
+    };
+
+    html! {
+        <div
+            ref={container}
+            class="relative bg-gradient-to-br from-amber-50 to-orange-100 rounded-2xl p-2 shadow-lg h-[480px] md:h-[500px]"
+        >
+            <div class="relative w-full h-full rounded-xl overflow-hidden">
+                { match location.map_image_url() {
+                    Some(src) => html! {
+                        <img
+                            src={src}
+                            alt={format!("Карта: {}", location.address)}
+                            loading="lazy"
+                            class="absolute inset-0 w-full h-full object-cover"
+                        />
+                    },
+                    None => html! {
+                        <div class="absolute inset-x-0 top-0 flex flex-col items-center gap-3 px-6 pt-16 text-center text-amber-800 font-body">
+                            <i class="fas fa-map-marker-alt text-4xl text-amber-600" aria-hidden="true"></i>
+                            <p class="text-lg">{ &location.address }</p>
+                        </div>
+                    },
+                }}
+                { if *requested {
+                    html! {
+                        <iframe
+                            src={location.iframe_url()}
+                            title={format!("Карта: {}", location.address)}
+                            onload={onload}
+                            width="100%"
+                            height="100%"
+                            style="border:0;"
+                            allowfullscreen={true}
+                            referrerpolicy="no-referrer-when-downgrade"
+                            class={format!(
+                                "absolute inset-0 transition-opacity duration-500 {}",
+                                if *loaded { "opacity-100" } else { "opacity-0" }
+                            )}
+                        ></iframe>
+                    }
+                } else {
+                    html! {
+                        <button
+                            onclick={onrequest}
+                            class="absolute inset-0 flex items-center justify-center bg-amber-900/10 hover:bg-amber-900/20 transition-colors"
+                        >
+                            <span class="bg-white text-amber-800 px-6 py-3 rounded-full font-body font-semibold shadow-lg">
+                                <i class="fas fa-map-marked-alt mr-2" aria-hidden="true"></i>
+                                { "Показать карту" }
+                            </span>
+                        </button>
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
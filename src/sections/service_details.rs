@@ -0,0 +1,112 @@
+use crate::data;
+use crate::markdown;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ServiceDetailsCardProps {
+    pub item: data::ServiceItem,
+}
+
+/// Раскрывающиеся подробности услуги: фото, состав, противопоказания, уход и вопросы.
+#[function_component(ServiceDetailsCard)]
+pub fn service_details_card(props: &ServiceDetailsCardProps) -> Html {
+    let expanded = use_state(|| false);
+    let details = &props.item.details;
+    if details.is_empty() {
+        return html! {};
+    }
+
+    let panel_id = format!("service-details-{}", props.item.id);
+    let ontoggle = {
+        let expanded = expanded.clone();
+        Callback::from(move |_| expanded.set(!*expanded))
+    };
+
+    html! {
+        <div class="mt-2">
+            <button
+                onclick={ontoggle}
+                aria-expanded={expanded.to_string()}
+                aria-controls={panel_id.clone()}
+                class="text-sm text-amber-700 hover:text-amber-900 font-body font-semibold"
+            >
+                { if *expanded { "Свернуть" } else { "Подробнее" } }
+                <i class={format!("fas {} ml-1 text-xs", if *expanded { "fa-chevron-up" } else { "fa-chevron-down" })} aria-hidden="true"></i>
+            </button>
+
+            { if *expanded {
+                html! {
+                    <div id={panel_id} class="mt-3 space-y-4 text-sm text-amber-800 font-body">
+                        { if details.gallery.is_empty() {
+                            html! {}
+                        } else {
+                            html! {
+                                <div class="flex gap-2 overflow-x-auto pb-1">
+                                    { for details.gallery.iter().enumerate().map(|(i, src)| html! {
+                                        <img
+                                            src={src.clone()}
+                                            alt={format!(
+                                                "{} (фото {} из {})",
+                                                details.gallery_alt.as_deref().unwrap_or(&props.item.name),
+                                                i + 1,
+                                                details.gallery.len()
+                                            )}
+                                            loading="lazy"
+                                            class="h-24 w-24 object-cover rounded-lg shrink-0"
+                                        />
+                                    }) }
+                                </div>
+                            }
+                        }}
+                        { for details.result_lasts.iter().map(|lasts| html! {
+                            <p>
+                                <i class="fas fa-hourglass-half mr-2 text-amber-600" aria-hidden="true"></i>
+                                { format!("Результат держится: {}", lasts) }
+                            </p>
+                        }) }
+                        { render_detail_list("Что входит", "fa-check text-green-600", &details.includes) }
+                        { render_detail_list("Противопоказания", "fa-exclamation-triangle text-red-500", &details.contraindications) }
+                        { render_detail_list("Уход после процедуры", "fa-leaf text-amber-600", &details.aftercare) }
+                        { if details.faq.is_empty() {
+                            html! {}
+                        } else {
+                            html! {
+                                <div>
+                                    <h5 class="font-heading font-semibold text-amber-900 mb-1">{ "Частые вопросы" }</h5>
+                                    { for details.faq.iter().map(|faq| html! {
+                                        <details class="border-b border-amber-100 py-1">
+                                            <summary class="cursor-pointer font-semibold">{ &faq.question }</summary>
+                                            <div class="mt-1 text-amber-700 space-y-1">{ markdown::render(&faq.answer) }</div>
+                                        </details>
+                                    }) }
+                                </div>
+                            }
+                        }}
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+fn render_detail_list(title: &str, icon: &str, items: &[String]) -> Html {
+    if items.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div>
+            <h5 class="font-heading font-semibold text-amber-900 mb-1">{ title }</h5>
+            <ul class="space-y-1">
+                { for items.iter().map(|item| html! {
+                    <li class="flex items-start">
+                        <i class={format!("fas {} mr-2 mt-1 text-xs", icon)} aria-hidden="true"></i>
+                        <span>{ item }</span>
+                    </li>
+                }) }
+            </ul>
+        </div>
+    }
+}
@@ -0,0 +1,86 @@
+use crate::data;
+use crate::date;
+use crate::render_stars;
+use yew::prelude::*;
+
+/// Отзывы длиннее этого числа символов сворачиваются до нескольких строк.
+const TESTIMONIAL_CLAMP_CHARS: usize = 220;
+
+#[derive(Properties, PartialEq)]
+pub struct TestimonialCardProps {
+    pub testimonial: data::Testimonial,
+    pub today: date::CalendarDate,
+}
+
+#[function_component(TestimonialCard)]
+pub fn testimonial_card(props: &TestimonialCardProps) -> Html {
+    let testimonial = &props.testimonial;
+    let expanded = use_state(|| false);
+    let is_long = testimonial.text.chars().count() > TESTIMONIAL_CLAMP_CHARS;
+
+    let ontoggle = {
+        let expanded = expanded.clone();
+        Callback::from(move |_| expanded.set(!*expanded))
+    };
+
+    html! {
+        <div class="bg-white rounded-2xl shadow-lg hover:shadow-2xl transition-all motion-reduce:transition-none duration-300 p-6 transform motion-safe:hover:-translate-y-2 h-full flex flex-col">
+            // Аватар и имя
+            <div class="flex items-center mb-4">
+                <div class="w-14 h-14 rounded-full bg-gradient-to-br from-amber-400 to-orange-500 flex items-center justify-center text-white font-heading font-bold text-lg mr-4">
+                    { testimonial.avatar_text() }
+                </div>
+                <div>
+                    <h3 class="font-heading font-bold text-amber-900 text-lg">
+                        { &testimonial.name }
+                    </h3>
+                    if !testimonial.service.is_empty() {
+                        <p class="text-sm text-amber-600 font-body">
+                            { &testimonial.service }
+                        </p>
+                    }
+                </div>
+            </div>
+
+            // Рейтинг
+            <div class="mb-3">
+                { render_stars(testimonial.rating, "text-amber-500") }
+            </div>
+
+            // Текст отзыва
+            <div class="relative mb-4 flex-1">
+                <i class="fas fa-quote-left text-3xl text-amber-200 absolute -top-2 -left-1" aria-hidden="true"></i>
+                <p class={classes!(
+                    "text-amber-800", "font-body", "leading-relaxed", "pl-8", "pr-4",
+                    (is_long && !*expanded).then_some("line-clamp-4")
+                )}>
+                    { &testimonial.text }
+                </p>
+                <i class="fas fa-quote-right text-3xl text-amber-200 absolute -bottom-2 right-0" aria-hidden="true"></i>
+            </div>
+
+            { if is_long {
+                html! {
+                    <button
+                        onclick={ontoggle}
+                        aria-expanded={expanded.to_string()}
+                        class="self-start pl-8 mb-3 text-sm font-body font-semibold text-amber-600 hover:text-amber-800 transition-colors"
+                    >
+                        { if *expanded { "Свернуть" } else { "Читать полностью" } }
+                    </button>
+                }
+            } else {
+                html! {}
+            }}
+
+            // Дата
+            <time
+                datetime={testimonial.date.to_iso()}
+                title={testimonial.date.format_ru()}
+                class="text-sm text-amber-600 font-body text-right"
+            >
+                { testimonial.date.relative_ru(props.today) }
+            </time>
+        </div>
+    }
+}
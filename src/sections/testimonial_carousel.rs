@@ -0,0 +1,200 @@
+use crate::data;
+use crate::date;
+use crate::sections::testimonial_card::TestimonialCard;
+use crate::viewport;
+use gloo_timers::callback::Interval;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct TestimonialCarouselProps {
+    pub items: Vec<data::Testimonial>,
+    pub autoplay_seconds: u32,
+    pub today: date::CalendarDate,
+}
+
+#[function_component(TestimonialCarousel)]
+pub fn testimonial_carousel(props: &TestimonialCarouselProps) -> Html {
+    let current = use_state(|| 0usize);
+    // Автопрокрутка стоит, пока отзыв читают: под курсором или с фокусом внутри
+    let hovered = use_state(|| false);
+    let focused = use_state(|| false);
+    let touch_start = use_mut_ref(|| None::<i32>);
+
+    let count = props.items.len();
+    let index = (*current).min(count.saturating_sub(1));
+    let paused = *hovered || *focused;
+
+    {
+        let current = current.clone();
+        // Автопрокрутка — тоже анимация, при «уменьшить движение» она выключена
+        let autoplay_seconds = if viewport::prefers_reduced_motion() {
+            0
+        } else {
+            props.autoplay_seconds
+        };
+        use_effect_with((paused, count, index), move |&(paused, count, index)| {
+            let interval = (!paused && count > 1 && autoplay_seconds > 0).then(|| {
+                Interval::new(autoplay_seconds * 1000, move || {
+                    current.set((index + 1) % count);
+                })
+            });
+            move || drop(interval)
+        });
+    }
+
+    let show = {
+        let current = current.clone();
+        move |delta: isize| {
+            if count > 1 {
+                current.set((index as isize + delta).rem_euclid(count as isize) as usize);
+            }
+        }
+    };
+
+    let onkeydown = {
+        let show = show.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowLeft" => {
+                e.prevent_default();
+                show(-1);
+            }
+            "ArrowRight" => {
+                e.prevent_default();
+                show(1);
+            }
+            _ => {}
+        })
+    };
+    let ontouchstart = {
+        let touch_start = touch_start.clone();
+        Callback::from(move |e: TouchEvent| {
+            *touch_start.borrow_mut() = e.touches().get(0).map(|t| t.client_x());
+        })
+    };
+    let ontouchend = {
+        let show = show.clone();
+        Callback::from(move |e: TouchEvent| {
+            let start = touch_start.borrow_mut().take();
+            let end = e.changed_touches().get(0).map(|t| t.client_x());
+            if let (Some(start), Some(end)) = (start, end) {
+                if end - start > 40 {
+                    show(-1);
+                } else if start - end > 40 {
+                    show(1);
+                }
+            }
+        })
+    };
+    let onmouseenter = {
+        let hovered = hovered.clone();
+        Callback::from(move |_: MouseEvent| hovered.set(true))
+    };
+    let onmouseleave = {
+        let hovered = hovered.clone();
+        Callback::from(move |_: MouseEvent| hovered.set(false))
+    };
+    let onfocusin = {
+        let focused = focused.clone();
+        Callback::from(move |_: FocusEvent| focused.set(true))
+    };
+    let onfocusout = {
+        let focused = focused.clone();
+        Callback::from(move |e: FocusEvent| {
+            // Фокус перешёл на другую кнопку карусели — это не уход из неё
+            let carousel = e
+                .current_target()
+                .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+            let next = e
+                .related_target()
+                .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+            if !carousel.is_some_and(|carousel| carousel.contains(next.as_ref())) {
+                focused.set(false);
+            }
+        })
+    };
+    let onprev = {
+        let show = show.clone();
+        Callback::from(move |_: MouseEvent| show(-1))
+    };
+    let onnext = Callback::from(move |_: MouseEvent| show(1));
+
+    if count == 0 {
+        return html! {};
+    }
+
+    html! {
+        <div
+            class="relative max-w-2xl mx-auto focus:outline-none focus-visible:ring-4 focus-visible:ring-amber-300 rounded-2xl"
+            tabindex="0"
+            role="region"
+            aria-roledescription="карусель"
+            aria-label="Отзывы клиентов"
+            onkeydown={onkeydown}
+            onmouseenter={onmouseenter}
+            onmouseleave={onmouseleave}
+            onfocusin={onfocusin}
+            onfocusout={onfocusout}
+            ontouchstart={ontouchstart}
+            ontouchend={ontouchend}
+        >
+            <div class="overflow-hidden rounded-2xl">
+                <div
+                    class="flex transition-transform motion-reduce:transition-none duration-500 ease-out"
+                    style={format!("transform: translateX(-{}%);", index * 100)}
+                >
+                    { for props.items.iter().enumerate().map(|(i, testimonial)| html! {
+                        <div
+                            key={testimonial.key()}
+                            class="w-full flex-shrink-0 p-2"
+                            aria-hidden={(i != index).to_string()}
+                            inert={(i != index).then_some("")}
+                            aria-roledescription="слайд"
+                        >
+                            <TestimonialCard testimonial={testimonial.clone()} today={props.today} />
+                        </div>
+                    }) }
+                </div>
+            </div>
+
+            { if count > 1 {
+                html! {
+                    <div class="flex items-center justify-center gap-4 mt-6">
+                        <button
+                            onclick={onprev}
+                            aria-label="Предыдущий отзыв"
+                            class="w-10 h-10 rounded-full bg-white text-amber-700 shadow hover:bg-amber-100 transition-colors"
+                        >
+                            <i class="fas fa-chevron-left" aria-hidden="true"></i>
+                        </button>
+                        <div class="flex gap-2">
+                            { for (0..count).map(|i| {
+                                let current = current.clone();
+                                html! {
+                                    <button
+                                        onclick={Callback::from(move |_| current.set(i))}
+                                        aria-label={format!("Отзыв {} из {}", i + 1, count)}
+                                        aria-current={(i == index).to_string()}
+                                        class={format!(
+                                            "w-2.5 h-2.5 rounded-full transition-colors {}",
+                                            if i == index { "bg-amber-600" } else { "bg-amber-300 hover:bg-amber-400" }
+                                        )}
+                                    ></button>
+                                }
+                            }) }
+                        </div>
+                        <button
+                            onclick={onnext}
+                            aria-label="Следующий отзыв"
+                            class="w-10 h-10 rounded-full bg-white text-amber-700 shadow hover:bg-amber-100 transition-colors"
+                        >
+                            <i class="fas fa-chevron-right" aria-hidden="true"></i>
+                        </button>
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
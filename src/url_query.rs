@@ -0,0 +1,126 @@
+use web_sys::UrlSearchParams;
+
+/// Значение параметра из query-строки текущего адреса.
+pub fn get(name: &str) -> Option<String> {
+    params()?.get(name)
+}
+
+/// Все значения повторяющегося параметра: `?tag=a&tag=b`.
+pub fn get_list(name: &str) -> Vec<String> {
+    params()
+        .map(|params| {
+            params
+                .get_all(name)
+                .iter()
+                .filter_map(|value| value.as_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Записывает список значений повторяющимся параметром; пустой список удаляет его.
+pub fn set_list(name: &str, values: &[String]) {
+    let Some(params) = params() else {
+        return;
+    };
+
+    params.delete(name);
+    for value in values {
+        params.append(name, value);
+    }
+
+    replace_search(&String::from(params.to_string()));
+}
+
+/// Обновляет параметры адресной строки без перезагрузки страницы.
+/// `None` удаляет параметр, остальные параметры сохраняются.
+pub fn set_all(values: &[(&str, Option<&str>)]) {
+    let Some(params) = params() else {
+        return;
+    };
+
+    for (name, value) in values {
+        match value {
+            Some(value) => params.set(name, value),
+            None => params.delete(name),
+        }
+    }
+
+    replace_search(&String::from(params.to_string()));
+}
+
+/// Якорь текущего адреса без `#`.
+pub fn hash() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let hash = hash.trim_start_matches('#');
+    (!hash.is_empty()).then(|| hash.to_string())
+}
+
+/// Меняет якорь без прокрутки к нему и без новой записи в истории.
+pub fn set_hash(hash: Option<&str>) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let location = window.location();
+    let path = location.pathname().unwrap_or_default();
+    let search = location.search().unwrap_or_default();
+    let url = match hash {
+        Some(hash) => format!("{}{}#{}", path, search, hash),
+        None => format!("{}{}", path, search),
+    };
+
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+    }
+}
+
+fn params() -> Option<UrlSearchParams> {
+    let search = web_sys::window()?.location().search().ok()?;
+    UrlSearchParams::new_with_str(&search).ok()
+}
+
+fn replace_search(search: &str) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let location = window.location();
+    let path = location.pathname().unwrap_or_default();
+    let hash = location.hash().unwrap_or_default();
+    let url = if search.is_empty() {
+        format!("{}{}", path, hash)
+    } else {
+        format!("{}?{}{}", path, search, hash)
+    };
+
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+    }
+}
+
+/// Адрес текущей страницы без параметров и якоря.
+pub fn page_url() -> Option<String> {
+    let location = web_sys::window()?.location();
+    Some(format!(
+        "{}{}",
+        location.origin().ok()?,
+        location.pathname().ok()?
+    ))
+}
+
+/// Кодирование значения параметра URL, как `encodeURIComponent`.
+pub fn encode_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Ссылка для скачивания текстового файла без обращения к серверу.
+pub fn data_url(mime: &str, text: &str) -> String {
+    format!("data:{};charset=utf-8,{}", mime, encode_component(text))
+}
@@ -0,0 +1,116 @@
+use crate::data::{Contacts, SalonInfo};
+
+/// Длина строки vCard и iCalendar в байтах; длинные строки переносятся (RFC 6350, 5545).
+const LINE_LIMIT: usize = 75;
+
+/// Визитка салона в формате vCard 3.0 — её понимают iOS, Android и Outlook.
+pub fn salon_card(info: &SalonInfo, contacts: &Contacts) -> String {
+    let phone = contacts
+        .phone
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '+')
+        .collect::<String>();
+    let site = info.site_url.as_deref().unwrap_or(&contacts.link);
+
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!("FN:{}", escape(&info.name)),
+        format!("N:{};;;;", escape(&info.name)),
+        format!("ORG:{}", escape(&info.name)),
+        format!("TEL;TYPE=CELL,VOICE:{}", phone),
+        format!("EMAIL;TYPE=INTERNET:{}", escape(&contacts.email)),
+        format!(
+            "ADR;TYPE=WORK:;;{};{};;;Россия",
+            escape(&contacts.address),
+            escape(&info.city)
+        ),
+        format!("URL:{}", escape(site)),
+        format!(
+            "NOTE:{}\\n{}",
+            escape(&info.slogan),
+            escape(&contacts.working_hours)
+        ),
+    ];
+    for (network, link) in [
+        ("telegram", &contacts.telegram),
+        ("whatsapp", &contacts.whatsapp),
+        ("instagram", &contacts.instagram),
+        ("vk", &contacts.vk),
+    ] {
+        if !link.is_empty() {
+            lines.push(format!("X-SOCIALPROFILE;TYPE={}:{}", network, escape(link)));
+        }
+    }
+    lines.push("END:VCARD".to_string());
+
+    fold_lines(&lines)
+}
+
+/// Экранирование значения: `\`, `,`, `;` и переводы строк.
+pub fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ',' => escaped.push_str("\\,"),
+            ';' => escaped.push_str("\\;"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Строки через CRLF; строки длиннее 75 байт переносятся с пробелом в начале
+/// продолжения, не разрывая символы UTF-8.
+pub fn fold_lines(lines: &[String]) -> String {
+    let mut result = String::new();
+    for line in lines {
+        let mut width = 0;
+        for c in line.chars() {
+            if width + c.len_utf8() > LINE_LIMIT {
+                result.push_str("\r\n ");
+                width = 1;
+            }
+            result.push(c);
+            width += c.len_utf8();
+        }
+        result.push_str("\r\n");
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(escape("a,b;c\\d"), "a\\,b\\;c\\\\d");
+        assert_eq!(escape("строка\r\nещё"), "строка\\nещё");
+        assert_eq!(escape("без изменений"), "без изменений");
+    }
+
+    #[test]
+    fn short_lines_are_not_folded() {
+        let lines = ["BEGIN:VCARD".to_string(), "END:VCARD".to_string()];
+        assert_eq!(fold_lines(&lines), "BEGIN:VCARD\r\nEND:VCARD\r\n");
+    }
+
+    #[test]
+    fn folds_by_bytes_without_splitting_characters() {
+        let line = format!("NOTE:{}", "ж".repeat(100));
+        let folded = fold_lines(std::slice::from_ref(&line));
+
+        let parts = folded
+            .trim_end_matches("\r\n")
+            .split("\r\n")
+            .collect::<Vec<_>>();
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|part| part.len() <= LINE_LIMIT));
+        assert!(parts[1..].iter().all(|part| part.starts_with(' ')));
+        assert_eq!(folded.replace("\r\n ", ""), format!("{}\r\n", line));
+    }
+}
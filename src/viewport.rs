@@ -0,0 +1,78 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+
+/// Отслеживание появления элемента на экране; отключается при удалении.
+pub struct VisibilityObserver {
+    observer: IntersectionObserver,
+    _callback: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl Drop for VisibilityObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// Вызывает `on_visible` один раз, когда элемент окажется ближе `margin` к экрану.
+pub fn observe_once(
+    element: &Element,
+    margin: &str,
+    on_visible: impl FnOnce() + 'static,
+) -> Option<VisibilityObserver> {
+    let mut on_visible = Some(on_visible);
+    observe(element, margin, move |visible| {
+        if let Some(on_visible) = on_visible.take_if(|_| visible) {
+            on_visible();
+        }
+    })
+}
+
+/// Сообщает о каждом появлении элемента на экране и уходе с него.
+pub fn observe(
+    element: &Element,
+    margin: &str,
+    mut on_change: impl FnMut(bool) + 'static,
+) -> Option<VisibilityObserver> {
+    let callback = Closure::<dyn FnMut(js_sys::Array)>::new(move |entries: js_sys::Array| {
+        let visible = entries.iter().any(|entry| {
+            entry
+                .dyn_into::<IntersectionObserverEntry>()
+                .is_ok_and(|entry| entry.is_intersecting())
+        });
+        on_change(visible);
+    });
+
+    let options = IntersectionObserverInit::new();
+    options.set_root_margin(margin);
+    let observer =
+        IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options).ok()?;
+    observer.observe(element);
+    Some(VisibilityObserver {
+        observer,
+        _callback: callback,
+    })
+}
+
+/// Браузер умеет следить за появлением элементов на экране.
+pub fn is_supported() -> bool {
+    web_sys::window().is_some_and(|window| {
+        js_sys::Reflect::has(&window, &JsValue::from_str("IntersectionObserver")).unwrap_or(false)
+    })
+}
+
+/// Посетитель попросил систему не показывать лишнюю анимацию.
+pub fn prefers_reduced_motion() -> bool {
+    matches_media("(prefers-reduced-motion: reduce)")
+}
+
+/// Устройство без наведения курсора: телефон или планшет.
+pub fn is_touch_only() -> bool {
+    matches_media("(hover: none)")
+}
+
+fn matches_media(query: &str) -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media(query).ok().flatten())
+        .is_some_and(|query| query.matches())
+}
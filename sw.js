@@ -0,0 +1,102 @@
+// Сервис-воркер: сайт открывается без сети с последними загруженными
+// контактами и ценами. Список файлов и имя кэша (хэш списка) собирает
+// `cargo run -- pwa`: с новым списком старый кэш удаляется при активации.
+const CACHE = "yainna-e091e40ce2d0";
+const PRECACHE_LIST = "/static/pwa/precache.json";
+
+self.addEventListener("install", (event) => {
+    event.waitUntil(
+        (async () => {
+            const cache = await caches.open(CACHE);
+            const urls = ["/", ...(await precacheList()), ...(await bundleFiles())];
+            // Один недоступный файл не должен срывать установку
+            await Promise.all(urls.map((url) => cache.add(url).catch(() => undefined)));
+            await self.skipWaiting();
+        })(),
+    );
+});
+
+self.addEventListener("activate", (event) => {
+    event.waitUntil(
+        (async () => {
+            const names = await caches.keys();
+            await Promise.all(names.filter((name) => name !== CACHE).map((name) => caches.delete(name)));
+            await self.clients.claim();
+        })(),
+    );
+});
+
+self.addEventListener("fetch", (event) => {
+    const request = event.request;
+    const url = new URL(request.url);
+    if (request.method !== "GET" || url.origin !== self.location.origin) {
+        return;
+    }
+    // Все страницы сайта — одно приложение: без сети отдаётся сохранённая главная
+    const fallback = request.mode === "navigate" ? "/" : undefined;
+    event.respondWith(staleWhileRevalidate(event, request, fallback));
+});
+
+// Сразу отдаёт сохранённую копию и обновляет её в фоне; при следующем
+// открытии будут уже новые цены и тексты.
+async function staleWhileRevalidate(event, request, fallback) {
+    const cache = await caches.open(CACHE);
+    const cached = await cache.match(request, { ignoreSearch: request.mode === "navigate" });
+    const network = fetch(request)
+        .then((response) => {
+            if (response.ok) {
+                cache.put(request, response.clone());
+                if (request.mode === "navigate") {
+                    response.clone().text().then((html) => pruneBundles(cache, html));
+                }
+            }
+            return response;
+        })
+        .catch(() => undefined);
+
+    if (cached) {
+        event.waitUntil(network);
+        return cached;
+    }
+    const response = await network;
+    if (response) {
+        return response;
+    }
+    return (fallback && (await cache.match(fallback))) || Response.error();
+}
+
+async function precacheList() {
+    try {
+        const response = await fetch(PRECACHE_LIST, { cache: "no-cache" });
+        return response.ok ? await response.json() : [];
+    } catch {
+        return [];
+    }
+}
+
+// JS и wasm сборки: Trunk добавляет в имена хэш, поэтому они берутся из index.html.
+async function bundleFiles() {
+    try {
+        return bundleUrls(await (await fetch("/", { cache: "no-cache" })).text());
+    } catch {
+        return [];
+    }
+}
+
+function bundleUrls(html) {
+    return [...html.matchAll(/(?:href|src)="([^"]+\.(?:js|wasm))"/g)].map((match) => match[1]);
+}
+
+// После выпуска с новой сборкой копии прошлых JS и wasm больше не нужны:
+// без этого кэш рос бы с каждым выпуском.
+async function pruneBundles(cache, html) {
+    const current = new Set(bundleUrls(html).map((url) => new URL(url, self.location.origin).href));
+    if (current.size === 0) {
+        return;
+    }
+    const requests = await cache.keys();
+    const stale = requests.filter(
+        (request) => /\.(?:js|wasm)$/.test(new URL(request.url).pathname) && !current.has(request.url),
+    );
+    await Promise.all(stale.map((request) => cache.delete(request)));
+}